@@ -1,5 +1,8 @@
 fn main() {
-    let _gref = glug::GLogger::setup();
+    let _gref = glug::GLogger::setup_with_options(glug::GLoggerOptions {
+        panic_hook: true,
+        ..Default::default()
+    });
     for _ in 0..10 {
         log::warn!("message");
     }
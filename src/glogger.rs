@@ -1,5 +1,11 @@
+pub mod file;
+pub mod filter;
+mod json;
 mod macurses;
+mod queue;
+pub mod template;
 pub mod termpin;
+use filter::Filter;
 use log::{set_logger, warn, Level, Log, Record};
 pub use macurses::Ansi8;
 use macurses::Ansi8::*;
@@ -8,34 +14,126 @@ use options::GStoreOptions;
 use std::cmp::max;
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
-use std::mem::swap;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc;
 use std::sync::mpsc::channel;
-use std::sync::Arc;
 use std::sync::OnceLock;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, RwLock, Weak};
+use std::thread;
 use std::thread::{JoinHandle, ThreadId};
-use std::{thread, usize};
+use std::time::{Duration, Instant};
+use termpin::screen::Screen;
 use termpin::Box2D;
-type LogMessage = Result<(String, Level, GLoggerOptionalInfo), GLoggerSignal>;
+type LogMessage = Result<GRecord, GLoggerSignal>;
+///sinks opened for a writer thread.
+type Opened = Vec<(Box<dyn Write + Send>, options::Sink)>;
+///how often the writer checks whether the terminal was resized.
+const RESIZE_POLL: Duration = Duration::from_millis(250);
 ///The logger. Use `setup` or `setup_with_options` to initiate and `end` to stop.
+///
+///Each setup makes a new `GLogger` owned by the returned `GLoggerRef`. Once that is dropped, glug
+///can be set up again.
 pub struct GLogger {
-    channel: OnceLock<mpsc::Sender<LogMessage>>,
-    enabled: OnceLock<GLoggerOptionalQuestions>,
+    channel: queue::Sender,
+    enabled: GLoggerOptionalQuestions,
+    filter: RwLock<Filter>,
+    flush_timeout: Duration,
+    shutdown_timeout: Option<Duration>,
+    after_shutdown: (options::AfterShutdown, options::Format),
+    ///for records handled by `after_shutdown`, which the writer thread doesn't see.
+    timestamp_format: options::TimestampFormat,
+    started: chrono::DateTime<chrono::Local>,
+    ///set by `end`, after which records aren't sent to the writer thread.
+    stopped: AtomicBool,
+    ///set once the writer thread has ended and closed its files.
+    finished: (Mutex<bool>, Condvar),
+    panic_hook: bool,
+    #[cfg(feature = "signals")]
+    on_signal: Option<options::OnSignal>,
+    ///whether the writer thread draws graphics, which need undoing if it panics.
+    terminal: bool,
+    discarded: AtomicUsize,
+}
+///Why the logger couldn't be set up, from `GLogger::try_setup_with_options`.
+///
+///No file is opened and no hook installed when setup fails. After `ExistingLogger` and
+///`NoTerminal` another logger can be used instead; after other errors glug stays the `log`
+///logger, logging nothing until it's set up again.
+///# Examples
+///```
+///let options = glug::GLoggerOptions::<std::thread::ThreadId> {
+///    output: glug::options::Output::Terminal,
+///    ..Default::default()
+///};
+///match glug::GLogger::try_setup_with_options(options) {
+///    Ok(_gref) => log::info!("logged a message"),
+///    Err(glug::GlugError::ExistingLogger | glug::GlugError::NoTerminal) => {
+///        //set up another logger here
+///    }
+///    Err(e) => panic!("{}", e),
+///}
+///```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GlugError {
+    ///another logger was set, or a glug logger is still running.
+    ExistingLogger,
+    ///`GLoggerOptions::save_to_file` or a sink couldn't be opened.
+    FileOpen {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    ///`options::Output::Terminal` was asked for, but the terminal size is unknown.
+    NoTerminal,
+    ///the writer thread couldn't be started.
+    ThreadSpawn(std::io::Error),
+    ///the handler for `GLoggerOptions::on_signal` couldn't be installed, or glug was built
+    ///without the `signals` feature.
+    Signals(std::io::Error),
+    ///a `ReloadHandle` was used after its `GLoggerRef` was dropped.
+    Dropped,
+}
+impl Display for GlugError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ExistingLogger => write!(f, "a logger was already set"),
+            Self::FileOpen { path, source } => {
+                write!(
+                    f,
+                    "failed to open log file `{}`: {}",
+                    path.display(),
+                    source
+                )
+            }
+            Self::NoTerminal => write!(f, "the terminal size is unknown"),
+            Self::ThreadSpawn(source) => write!(f, "failed to start the writer thread: {}", source),
+            Self::Signals(source) => write!(f, "failed to handle signals: {}", source),
+            Self::Dropped => write!(f, "the logger was already dropped"),
+        }
+    }
+}
+impl std::error::Error for GlugError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::FileOpen { source, .. } | Self::ThreadSpawn(source) => Some(source),
+            Self::Signals(source) => Some(source),
+            Self::ExistingLogger | Self::NoTerminal | Self::Dropped => None,
+        }
+    }
 }
 #[derive(Debug)]
 struct GLoggerOptionalQuestions {
     thread_fingerprint: Option<()>,
     timestamp: Option<()>,
+    location: Option<()>,
 }
 impl<T: Eq + Hash + Debug> From<&GLoggerOptions<T>> for GLoggerOptionalQuestions {
     fn from(value: &GLoggerOptions<T>) -> Self {
         Self {
-            thread_fingerprint: if let Some(_) = &value.record_threads {
-                Some(())
-            } else {
-                None
-            },
+            thread_fingerprint: value.record_threads.as_ref().map(|_| ()),
             timestamp: value.timestamps,
+            location: value.locations,
         }
     }
 }
@@ -61,9 +159,57 @@ impl<T: Eq + Hash + Debug> From<&GLoggerOptions<T>> for GLoggerOptionalQuestions
 ///         Ansi8::Yellow,
 ///         Ansi8::Yellow],
 ///     save_to_file: None,
+///     file_format: glug::options::FileFormat::Json,
+///     rotation: Some(glug::options::Rotation {
+///         max_bytes: Some(10_000_000),
+///         period: Some(glug::options::Period::Daily),
+///         keep: 7,
+///         naming: glug::options::Naming::Dated,
+///         compression: glug::options::Compression::None,
+///     }),
+///     file_options: glug::options::FileOptions {
+///         append: true,
+///         create_dirs: true,
+///         mode: Some(0o640),
+///         fsync: false,
+///     },
+///     sinks: vec![glug::options::Sink::file(
+///         "errors.log",
+///         log::LevelFilter::Error,
+///         glug::options::FileFormat::Text,
+///     )],
+///     reopen: glug::options::Reopen::After(std::time::Duration::from_secs(5)),
+///     flush_policy: glug::options::FlushPolicy {
+///         records: Some(1000),
+///         interval: Some(std::time::Duration::from_millis(200)),
+///         on_error: true,
+///     },
+///     on_write_error: Some(std::sync::Arc::new(|name, error| {
+///         eprintln!("can't write to {}: {}", name, error)
+///     })),
 ///     record_threads: None,
 ///     max_messages_per_loop: Some(100),
+///     queue_capacity: Some(10_000),
+///     overflow: glug::options::Overflow::DropBelow(log::Level::Warn),
+///     after_shutdown: glug::options::AfterShutdown::Stderr,
+///     panic_hook: true,
+///     on_signal: Some(glug::options::OnSignal::Reraise),
 ///     timestamps: Some(()),
+///     timestamp_format: glug::options::TimestampFormat {
+///         style: glug::options::TimestampStyle::Strftime("%H:%M:%S%.3f".to_string()),
+///         utc: false,
+///     },
+///     locations: Some(()),
+///     show_key_values: true,
+///     filter: glug::Filter::parse("info,my_crate::net=trace,hyper=warn").unwrap(),
+///     flush_timeout: std::time::Duration::from_secs(1),
+///     shutdown_timeout: Some(std::time::Duration::from_secs(30)),
+///     max_fps: Some(30),
+///     tick: None,
+///     output: glug::options::Output::Auto,
+///     format: std::sync::Arc::new(|record: &glug::GRecord| {
+///         format!("{} {}: {}", record.level, record.target, record.message)
+///     }),
 ///     terminal,
 ///};
 ///```
@@ -73,34 +219,535 @@ pub struct GLoggerOptions<T: Eq + Hash + Debug> {
     pub colors: [Ansi8; 5],
     ///what file to save logs to, if one is supplied.
     pub save_to_file: Option<String>,
+    ///how logs are written to `save_to_file`.
+    pub file_format: options::FileFormat,
+    ///when to move `save_to_file` aside and start a new one. `None` truncates it at setup and
+    ///lets it grow.
+    pub rotation: Option<options::Rotation>,
+    ///how `save_to_file` is opened: appending or truncating, making its directory, and its
+    ///permissions.
+    pub file_options: options::FileOptions,
+    ///more places to write records to, each with its own level and format.
+    pub sinks: Vec<options::Sink>,
+    ///what happens to `save_to_file` and sinks after writing to them fails.
+    pub reopen: options::Reopen,
+    ///when files and sinks, which are buffered, are flushed.
+    pub flush_policy: options::FlushPolicy,
+    ///called on the writer thread whenever writing to a file or sink fails. Failures are also
+    ///counted in the summary.
+    pub on_write_error: Option<options::OnWriteError>,
     ///How to record which threads log what messages, if at all.
     pub record_threads: Option<options::RecordThreadsOptions>,
     ///how many messages to read before printing them.
     pub max_messages_per_loop: Option<usize>,
+    ///how many records may wait for the writer thread. `None` lets the queue grow without limit,
+    ///and `Some(0)` is taken as `Some(1)`.
+    pub queue_capacity: Option<usize>,
+    ///what happens to records logged while the queue is full.
+    pub overflow: options::Overflow,
+    ///what happens to records logged after `GLogger::end`, or after the writer thread is gone.
+    pub after_shutdown: options::AfterShutdown,
+    ///installs a panic hook that logs the panic as an error, stops the writer thread so it restores
+    ///the terminal, and then writes the panic message and a backtrace below the last frame.
+    ///Any panic, even one caught with `std::panic::catch_unwind`, ends logging for the rest of the
+    ///process.
+    ///Other panic hooks set before setup still handle panics while no logger asking for it runs.
+    pub panic_hook: bool,
+    ///handles SIGINT and SIGTERM by ending logging and restoring the terminal first, until the
+    ///`GLoggerRef` is dropped or a reload doesn't ask for it. signal-hook can't put their default
+    ///action back then, so they only reach the program's own handlers from then on. Needs the
+    ///`signals` feature, without which setup returns `GlugError::Signals`.
+    pub on_signal: Option<options::OnSignal>,
     ///whether or not to record timestamps.
     pub timestamps: Option<()>,
+    ///how timestamps are written everywhere: the terminal, files and the summary.
+    pub timestamp_format: options::TimestampFormat,
+    ///whether or not to record the module path, file and line of each message.
+    pub locations: Option<()>,
+    ///whether key-values like `log::info!(user_id = 42; "login")` are shown in the terminal.
+    ///They are always recorded and written to files.
+    pub show_key_values: bool,
+    ///which messages to log, by target and level. Messages filtered out are never formatted.
+    ///Defaults to the `RUST_LOG` environment variable, or everything if it is unset, blank or
+    ///invalid.
+    pub filter: Filter,
+    ///how long `log::logger().flush()` waits for the writer thread before giving up.
+    pub flush_timeout: Duration,
+    ///how long a panic or signal waits for the writer thread to write everything, close its files
+    ///and finish compressing archives before going on. `None` waits as long as it takes.
+    pub shutdown_timeout: Option<Duration>,
+    ///the most times per second the terminal is redrawn. `None` redraws after every read.
+    pub max_fps: Option<u32>,
+    ///redraw at least this often even if nothing was logged, for elements that change with time.
+    pub tick: Option<Duration>,
+    ///whether to draw graphics or write plain lines. Defaults to deciding at setup.
+    pub output: options::Output,
+    ///turns a record into the line shown in the terminal and written to files.
+    pub format: options::Format,
     //how to log to the terminal, what draws to call
     pub terminal: termpin::DivNode<T>,
 }
 pub mod options {
     //!options to supply to `GLoggerOptions`.
     pub use super::macurses::Ansi8;
-    use super::GLoggerOptionalInfo;
-    use log::Level;
+    use super::GRecord;
+    use chrono::{DateTime, Local, SecondsFormat, Utc};
+    use log::LevelFilter;
     use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    ///A shareable formatter, see `GLoggerOptions::format`.
+    pub type Format = Arc<dyn Fn(&GRecord) -> String + Send + Sync>;
+    ///one JSON object per line with the level, RFC3339 timestamp, thread id and name, target,
+    ///module, file, line, message and key-values. Fields that weren't recorded are left out.
+    pub fn json_format(record: &GRecord) -> String {
+        super::json::to_json(record)
+    }
+    ///How records are written to files and sinks.
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    pub enum FileFormat {
+        ///the same as the terminal, with `GLoggerOptions::format`.
+        #[default]
+        Text,
+        ///JSON Lines, see `json_format`.
+        Json,
+        Template(super::template::Template),
+    }
+    impl FileFormat {
+        ///the formatter to give a `gstore::Writer`, `None` meaning the store's own.
+        pub fn format(&self) -> Option<Format> {
+            match self {
+                Self::Text => None,
+                Self::Json => Some(Arc::new(json_format)),
+                Self::Template(template) => Some(template.clone().into_format()),
+            }
+        }
+    }
+    ///When buffered files and sinks are flushed, besides on `log::logger().flush()` and when
+    ///logging ends. Whichever comes first flushes every writer.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct FlushPolicy {
+        ///after this many records.
+        pub records: Option<usize>,
+        ///this long after a record was written.
+        pub interval: Option<std::time::Duration>,
+        ///after every `Error` record.
+        pub on_error: bool,
+    }
+    impl Default for FlushPolicy {
+        ///within a second, and right after errors.
+        fn default() -> Self {
+            Self {
+                records: None,
+                interval: Some(std::time::Duration::from_secs(1)),
+                on_error: true,
+            }
+        }
+    }
+    ///called with a writer's name, like the path of a file, and why writing to it failed.
+    pub type OnWriteError = Arc<dyn Fn(&str, &std::io::Error) + Send + Sync>;
+    ///What happens to a file or sink after writing to it fails. Records logged while it is
+    ///failed are not written to it.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Reopen {
+        ///stop writing to it for good.
+        Never,
+        ///open it again, appending, at the first record or flush this long after it failed.
+        After(std::time::Duration),
+    }
+    impl Default for Reopen {
+        fn default() -> Self {
+            Self::After(std::time::Duration::from_secs(5))
+        }
+    }
+    ///A writer shared between clones of `GLoggerOptions`, for `SinkTarget::Writer`.
+    #[derive(Clone)]
+    pub struct SharedWriter(Arc<Mutex<Box<dyn Write + Send>>>);
+    impl SharedWriter {
+        pub fn new(writer: impl Write + Send + 'static) -> Self {
+            Self(Arc::new(Mutex::new(Box::new(writer))))
+        }
+    }
+    impl Write for SharedWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap_or_else(|e| e.into_inner()).write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.0.lock().unwrap_or_else(|e| e.into_inner()).flush()
+        }
+    }
+    ///Where a sink writes.
+    #[derive(Clone)]
+    pub enum SinkTarget {
+        ///a file, opened at setup.
+        File {
+            path: String,
+            rotation: Option<Rotation>,
+            options: FileOptions,
+        },
+        ///something already open, like a pipe or a socket.
+        Writer(SharedWriter),
+    }
+    impl SinkTarget {
+        ///what the target is called in errors: the path, or nothing for writers.
+        pub fn name(&self) -> String {
+            match self {
+                Self::File { path, .. } => path.clone(),
+                Self::Writer(_) => String::new(),
+            }
+        }
+        ///a target that opens the same place again without losing what was written: files are
+        ///appended to instead of truncated or archived.
+        pub fn reopened(&self) -> Self {
+            match self {
+                Self::File {
+                    path,
+                    rotation,
+                    options,
+                } => Self::File {
+                    path: path.clone(),
+                    rotation: rotation.clone(),
+                    options: FileOptions {
+                        append: true,
+                        ..options.clone()
+                    },
+                },
+                Self::Writer(writer) => Self::Writer(writer.clone()),
+            }
+        }
+        ///opens the file, or shares the writer.
+        pub fn open(&self) -> std::io::Result<Box<dyn Write + Send>> {
+            Ok(match self {
+                Self::File {
+                    path,
+                    rotation: Some(rotation),
+                    options,
+                } => Box::new(super::file::LogFile::open(
+                    path,
+                    rotation.clone(),
+                    options.clone(),
+                )?),
+                Self::File {
+                    path,
+                    rotation: None,
+                    options,
+                } => Box::new(super::file::Buffered::new(
+                    options.open(path)?,
+                    options.fsync,
+                )),
+                Self::Writer(writer) => Box::new(writer.clone()),
+            })
+        }
+    }
+    ///Somewhere records are written besides the terminal, see `GLoggerOptions::sinks`.
+    ///# Examples
+    ///```
+    ///use glug::options::{FileFormat, Sink};
+    ///use log::LevelFilter;
+    ///let sinks = vec![
+    ///    Sink::file("errors.log", LevelFilter::Error, FileFormat::Text),
+    ///    Sink::file("debug.log", LevelFilter::Trace, FileFormat::Json),
+    ///    Sink::writer(std::io::stdout(), LevelFilter::Warn, FileFormat::Text),
+    ///];
+    ///```
+    #[derive(Clone)]
+    pub struct Sink {
+        pub target: SinkTarget,
+        ///the least severe level written. Records the filter drops never reach sinks.
+        pub level: LevelFilter,
+        pub format: FileFormat,
+    }
+    impl Sink {
+        ///a file truncated at setup and never rotated. Set `target` for more.
+        pub fn file(path: impl Into<String>, level: LevelFilter, format: FileFormat) -> Self {
+            Self {
+                target: SinkTarget::File {
+                    path: path.into(),
+                    rotation: None,
+                    options: FileOptions::default(),
+                },
+                level,
+                format,
+            }
+        }
+        pub fn writer(
+            writer: impl Write + Send + 'static,
+            level: LevelFilter,
+            format: FileFormat,
+        ) -> Self {
+            Self {
+                target: SinkTarget::Writer(SharedWriter::new(writer)),
+                level,
+                format,
+            }
+        }
+    }
+    ///How log files are opened.
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    pub struct FileOptions {
+        ///add to what an earlier run wrote instead of starting empty.
+        pub append: bool,
+        ///create missing parent directories.
+        pub create_dirs: bool,
+        ///unix permissions for new files, like `0o640`. Ignored elsewhere.
+        pub mode: Option<u32>,
+        ///sync to disk every time the file is flushed, see `FlushPolicy`. Slow, but what's
+        ///flushed survives a power loss.
+        pub fsync: bool,
+    }
+    impl FileOptions {
+        pub fn open(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<std::fs::File> {
+            let path = path.as_ref();
+            if self.create_dirs {
+                if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(parent)?;
+                }
+            }
+            let mut options = std::fs::OpenOptions::new();
+            options.create(true);
+            match self.append {
+                true => options.append(true),
+                false => options.write(true).truncate(true),
+            };
+            #[cfg(unix)]
+            if let Some(mode) = self.mode {
+                std::os::unix::fs::OpenOptionsExt::mode(&mut options, mode);
+            }
+            options.open(path)
+        }
+    }
+    ///When a log file is rotated: moved to an archive name and started again empty.
+    ///Both limits can be set, and rotation happens at whichever comes first.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Rotation {
+        ///rotate before a record would make the file bigger than this.
+        pub max_bytes: Option<u64>,
+        ///rotate when a new hour or day begins.
+        pub period: Option<Period>,
+        ///how many archives to keep, deleting the oldest.
+        pub keep: usize,
+        pub naming: Naming,
+        pub compression: Compression,
+    }
+    impl Default for Rotation {
+        ///no limits, keeping 5 numbered archives.
+        fn default() -> Self {
+            Self {
+                max_bytes: None,
+                period: None,
+                keep: 5,
+                naming: Naming::Numbered,
+                compression: Compression::None,
+            }
+        }
+    }
+    ///How archives are compressed, on a background thread after rotating. `Gzip` needs the
+    ///`gzip` feature and `Zstd` the `zstd` feature, so matches need a wildcard arm.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+    #[non_exhaustive]
+    pub enum Compression {
+        #[default]
+        None,
+        ///`app.log.1.gz`.
+        #[cfg(feature = "gzip")]
+        Gzip,
+        ///`app.log.1.zst`.
+        #[cfg(feature = "zstd")]
+        Zstd,
+    }
+    impl Compression {
+        ///what compressed archives end with.
+        pub fn extension(&self) -> &'static str {
+            match self {
+                Self::None => "",
+                #[cfg(feature = "gzip")]
+                Self::Gzip => ".gz",
+                #[cfg(feature = "zstd")]
+                Self::Zstd => ".zst",
+            }
+        }
+    }
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Period {
+        Hourly,
+        Daily,
+    }
+    ///What archives of `app.log` are called.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+    pub enum Naming {
+        ///`app.log.1` is the newest, then `app.log.2` and so on.
+        #[default]
+        Numbered,
+        ///`app.2026-10-18.log`, or `app.2026-10-18T13.log` when rotating hourly, for the period
+        ///the logs were written in. Archives from the same period are counted, like
+        ///`app.2026-10-18.1.log`.
+        Dated,
+    }
+    ///picks which key a record is counted under in `GStore::counts_keyed`.
+    pub type KeyFn<K> = Box<dyn Fn(&GRecord) -> Option<K>>;
+    ///the format used by default: level, thread, timestamp, message then key-values.
+    pub fn default_format(record: &GRecord) -> String {
+        match record.key_values.is_empty() {
+            true => format!("{:<6}{} {}", record.level, record.info, record.message),
+            false => format!(
+                "{:<6}{} {} {}",
+                record.level,
+                record.info,
+                record.message,
+                record.key_values_text()
+            ),
+        }
+    }
     ///Options for how to record threads, including `separate_histograms` and `summary`.
     #[derive(Clone, Debug, PartialEq, Eq)]
     pub struct RecordThreadsOptions {
         ///use only with big terminal.
         pub separate_histograms: bool,
-        ///summary of logs printed at end of logging. Make sure the logger is quit properly.
+        ///summary of logs printed at end of logging, and written to files and sinks that aren't
+        ///JSON, see `GStore::report`. Make sure the logger is quit properly.
         pub summary: bool,
     }
+    ///What happens to a record logged while `GLoggerOptions::queue_capacity` records are waiting.
+    ///Dropped records are counted in the summary.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+    pub enum Overflow {
+        ///the logging thread waits for room. Records the writer thread logs itself are dropped.
+        #[default]
+        Block,
+        ///the record is dropped.
+        DropNewest,
+        ///the oldest waiting record is dropped to make room.
+        DropOldest,
+        ///records less severe than the level are dropped, and the rest wait like `Block`.
+        DropBelow(log::Level),
+    }
+    ///What happens to records logged once the writer thread can't take them.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+    pub enum AfterShutdown {
+        ///written to stderr as plain lines with `GLoggerOptions::format`.
+        #[default]
+        Stderr,
+        ///thrown away and counted by `GLogger::discarded`.
+        Discard,
+    }
+    ///What happens after glug ends on SIGINT or SIGTERM, see `GLoggerOptions::on_signal`.
+    #[derive(Clone)]
+    pub enum OnSignal {
+        ///raise the signal again with its default action, which ends the process.
+        Reraise,
+        ///call this with the signal instead, on a thread glug spawned for signals.
+        Call(Arc<dyn Fn(i32) + Send + Sync>),
+    }
+    ///Where and how logs are shown on stderr.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+    pub enum Output {
+        ///graphics if stderr is a terminal of known size, plain lines without colors otherwise.
+        #[default]
+        Auto,
+        ///graphics with the layout in `GLoggerOptions::terminal`.
+        Terminal,
+        ///one formatted line per log, for CI, services, and output piped to a file.
+        Plain { colors: bool },
+    }
+    impl Output {
+        ///decides what `Auto` means for this process.
+        pub fn resolve(self) -> Self {
+            use std::io::IsTerminal;
+            match self {
+                Self::Auto if std::io::stderr().is_terminal() && termsize::get().is_some() => {
+                    Self::Terminal
+                }
+                Self::Auto => Self::Plain { colors: false },
+                output => output,
+            }
+        }
+    }
+    ///How timestamps are written, see `GLoggerOptions::timestamp_format`.
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    pub struct TimestampFormat {
+        pub style: TimestampStyle,
+        ///write times in UTC instead of local time, here, in `{time:...}` template pieces and in JSON.
+        ///Has no effect on `Elapsed` and `Delta`.
+        pub utc: bool,
+    }
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    pub enum TimestampStyle {
+        ///a strftime pattern, like `%H:%M:%S%.3f`.
+        Strftime(String),
+        ///like `2026-10-18T12:00:00.123+02:00`.
+        #[default]
+        Rfc3339,
+        ///time since the logger was set up, like `+12.345s`.
+        Elapsed,
+        ///time since the previous message, like `+0.012s`.
+        Delta,
+    }
+    impl TimestampFormat {
+        ///writes `time`. `started` is when logging began and `previous` is the time of the
+        ///message before, if any.
+        pub fn render(
+            &self,
+            time: &DateTime<Local>,
+            started: &DateTime<Local>,
+            previous: Option<&DateTime<Local>>,
+        ) -> String {
+            use std::fmt::Write;
+            let since = |earlier: &DateTime<Local>| {
+                let millis = (*time - *earlier).num_milliseconds().max(0);
+                format!("+{}.{:03}s", millis / 1000, millis % 1000)
+            };
+            let rfc3339 = || match self.utc {
+                true => time
+                    .with_timezone(&Utc)
+                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+                false => time.to_rfc3339_opts(SecondsFormat::Millis, false),
+            };
+            match &self.style {
+                TimestampStyle::Strftime(pattern) => {
+                    let mut out = String::new();
+                    let written = match self.utc {
+                        true => write!(out, "{}", time.with_timezone(&Utc).format(pattern)),
+                        false => write!(out, "{}", time.format(pattern)),
+                    };
+                    match written {
+                        Ok(_) => out,
+                        //invalid patterns can't be rendered, but shouldn't panic the writer
+                        Err(_) => rfc3339(),
+                    }
+                }
+                TimestampStyle::Rfc3339 => rfc3339(),
+                TimestampStyle::Elapsed => since(started),
+                TimestampStyle::Delta => since(previous.unwrap_or(started)),
+            }
+        }
+    }
     pub struct GStoreOptions<'a, K: PartialEq> {
         pub log_colors: [usize; 5],
-        pub separate_log_counts: Option<Box<dyn Fn(GLoggerOptionalInfo) -> Option<K>>>,
-        pub writers: &'a mut [Result<Box<dyn Write>, std::io::Error>],
-        pub format: &'a dyn Fn((String, Level, GLoggerOptionalInfo)) -> String,
+        pub separate_log_counts: Option<KeyFn<K>>,
+        pub writers: &'a mut [super::gstore::Writer],
+        pub format: &'a dyn Fn(&GRecord) -> String,
+        pub timestamp_format: TimestampFormat,
+        ///whether the text kept for the terminal includes key-values.
+        pub show_key_values: bool,
+        pub reopen: Reopen,
+        ///called with the writer's name and the error whenever a writer fails.
+        pub on_write_error: Option<OnWriteError>,
+        pub flush_policy: FlushPolicy,
+    }
+    impl<K: PartialEq> Default for GStoreOptions<'_, K> {
+        ///no writers, no separate counts, and the default format and colors.
+        fn default() -> Self {
+            Self {
+                log_colors: [31, 33, 32, 34, 39],
+                separate_log_counts: None,
+                writers: &mut [],
+                format: &default_format,
+                timestamp_format: TimestampFormat::default(),
+                show_key_values: true,
+                reopen: Reopen::default(),
+                on_write_error: None,
+                flush_policy: FlushPolicy::default(),
+            }
+        }
     }
 }
 
@@ -125,113 +772,554 @@ impl<T: Eq + Hash + Debug + 'static> std::default::Default for GLoggerOptions<T>
         );
         Self {
             timestamps: Some(()),
+            timestamp_format: options::TimestampFormat::default(),
+            locations: Some(()),
+            show_key_values: true,
             colors: [Red, Yellow, Green, Blue, Default],
             save_to_file: None,
+            file_format: options::FileFormat::Text,
+            rotation: None,
+            file_options: options::FileOptions::default(),
+            sinks: vec![],
+            reopen: options::Reopen::default(),
+            flush_policy: options::FlushPolicy::default(),
+            on_write_error: None,
             record_threads: Some(options::RecordThreadsOptions {
                 separate_histograms: false,
                 summary: false,
             }),
             max_messages_per_loop: Some(100),
+            queue_capacity: None,
+            overflow: options::Overflow::Block,
+            after_shutdown: options::AfterShutdown::Stderr,
+            panic_hook: false,
+            on_signal: None,
+            filter: match Filter::from_env("RUST_LOG") {
+                Some(Ok(filter)) => filter,
+                _ => Filter::default(),
+            },
+            flush_timeout: Duration::from_secs(1),
+            shutdown_timeout: Some(Duration::from_secs(30)),
+            max_fps: Some(30),
+            tick: None,
+            output: options::Output::Auto,
+            format: Arc::new(options::default_format),
             terminal,
         }
     }
 }
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 enum GLoggerSignal {
-    Flush,
-    Stop,
+    ///acknowledged once everything sent before it is drawn and written.
+    Flush(mpsc::Sender<()>),
+    ///acknowledged once the terminal is restored and the files are closed, as the writer thread
+    ///ends.
+    Stop(Option<mpsc::Sender<()>>),
 }
-#[derive(Clone)]
+///A logged message along with where it came from. Given to formatters, and kept by `GStore`.
+#[derive(Clone, Debug)]
+pub struct GRecord {
+    pub message: String,
+    pub level: Level,
+    pub target: String,
+    pub module_path: Option<String>,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    ///structured key-values, in the order they were logged.
+    pub key_values: Vec<(String, GValue)>,
+    pub info: GLoggerOptionalInfo,
+}
+///A key-value's value, keeping numbers and bools apart from text.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GValue {
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Str(String),
+}
+impl From<log::kv::Value<'_>> for GValue {
+    fn from(value: log::kv::Value<'_>) -> Self {
+        if let Some(v) = value.to_bool() {
+            Self::Bool(v)
+        } else if let Some(v) = value.to_i64() {
+            Self::I64(v)
+        } else if let Some(v) = value.to_u64() {
+            Self::U64(v)
+        } else if let Some(v) = value.to_f64() {
+            Self::F64(v)
+        } else {
+            Self::Str(value.to_string())
+        }
+    }
+}
+impl Display for GValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(v) => write!(f, "{}", v),
+            Self::I64(v) => write!(f, "{}", v),
+            Self::U64(v) => write!(f, "{}", v),
+            Self::F64(v) => write!(f, "{}", v),
+            Self::Str(v) => write!(f, "{}", v),
+        }
+    }
+}
+struct KeyValueCollector(Vec<(String, GValue)>);
+impl<'kvs> log::kv::VisitSource<'kvs> for KeyValueCollector {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kvs>,
+        value: log::kv::Value<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        self.0.push((key.to_string(), value.into()));
+        Ok(())
+    }
+}
+impl GRecord {
+    ///for building records by hand, like when testing elements against a `GStore`.
+    pub fn new(level: Level, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            level,
+            target: String::new(),
+            module_path: None,
+            file: None,
+            line: None,
+            key_values: vec![],
+            info: GLoggerOptionalInfo::default(),
+        }
+    }
+    fn from_record(record: &Record, info: GLoggerOptionalInfo, location: bool) -> Self {
+        let mut key_values = KeyValueCollector(vec![]);
+        let _ = record.key_values().visit(&mut key_values);
+        Self {
+            message: record.args().to_string(),
+            level: record.level(),
+            target: record.target().to_string(),
+            module_path: record.module_path().filter(|_| location).map(str::to_owned),
+            file: record.file().filter(|_| location).map(str::to_owned),
+            line: record.line().filter(|_| location),
+            key_values: key_values.0,
+            info,
+        }
+    }
+    ///key-values as `key=value` pairs separated by spaces. Text with spaces, `=` or quotes is
+    ///quoted.
+    pub fn key_values_text(&self) -> String {
+        let pairs: Vec<String> = self
+            .key_values
+            .iter()
+            .map(|(key, value)| match value {
+                GValue::Str(text)
+                    if text.is_empty()
+                        || text.contains(|c: char| c.is_whitespace() || c == '=' || c == '"') =>
+                {
+                    format!("{}={:?}", key, text)
+                }
+                value => format!("{}={}", key, value),
+            })
+            .collect();
+        pairs.join(" ")
+    }
+    ///`file:line`, or the module path if the file is unknown, if locations are recorded.
+    pub fn location(&self) -> Option<String> {
+        match (&self.file, self.line, &self.module_path) {
+            (Some(file), Some(line), _) => Some(format!("{}:{}", file, line)),
+            (Some(file), None, _) => Some(file.clone()),
+            (None, _, module) => module.clone(),
+        }
+    }
+    ///the logging thread's id, if threads are recorded.
+    pub fn thread_id(&self) -> Option<ThreadId> {
+        self.info.thread_fingerprint.as_ref().map(|t| t.0)
+    }
+    ///the logging thread's name, if threads are recorded and it has one.
+    pub fn thread_name(&self) -> Option<&str> {
+        self.info.thread_fingerprint.as_ref()?.1.as_deref()
+    }
+    ///when the message was logged, if timestamps are recorded.
+    pub fn timestamp(&self) -> Option<&chrono::DateTime<chrono::Local>> {
+        self.info.timestamp.as_ref()
+    }
+    ///the timestamp as set by `GLoggerOptions::timestamp_format`, once the record is in a
+    ///`GStore`.
+    pub fn timestamp_text(&self) -> Option<&str> {
+        self.info.timestamp_text.as_deref()
+    }
+    ///writes the timestamp with a strftime pattern, in UTC if `TimestampFormat::utc` is set.
+    pub(crate) fn format_timestamp(&self, pattern: &str) -> Option<String> {
+        use std::fmt::Write;
+        let time = self.info.timestamp?;
+        let mut out = String::new();
+        match self.info.utc {
+            true => write!(out, "{}", time.with_timezone(&chrono::Utc).format(pattern)),
+            false => write!(out, "{}", time.format(pattern)),
+        }
+        .ok()?;
+        Some(out)
+    }
+    ///the timestamp as RFC3339 with milliseconds, ending in `Z` if `TimestampFormat::utc` is set.
+    pub(crate) fn timestamp_rfc3339(&self) -> Option<String> {
+        use chrono::SecondsFormat;
+        let time = self.info.timestamp?;
+        Some(match self.info.utc {
+            true => time
+                .with_timezone(&chrono::Utc)
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+            false => time.to_rfc3339_opts(SecondsFormat::Millis, false),
+        })
+    }
+    ///writes the timestamp as set by `format`, for formatters to use.
+    fn stamp(
+        &mut self,
+        format: &options::TimestampFormat,
+        started: &chrono::DateTime<chrono::Local>,
+        previous: Option<&chrono::DateTime<chrono::Local>>,
+    ) {
+        if let Some(time) = self.info.timestamp {
+            self.info.timestamp_text = Some(format.render(&time, started, previous));
+            self.info.utc = format.utc;
+        }
+    }
+}
+///Info recorded with a message depending on `GLoggerOptions`.
+#[derive(Clone, Debug, Default)]
 pub struct GLoggerOptionalInfo {
     thread_fingerprint: Option<(ThreadId, Option<String>)>,
     timestamp: Option<chrono::DateTime<chrono::Local>>,
+    timestamp_text: Option<String>,
+    ///whether the timestamp is written in UTC.
+    utc: bool,
+}
+impl GLoggerOptionalInfo {
+    ///for building records by hand, like when testing elements against a `GStore`.
+    pub fn new(
+        thread_fingerprint: Option<(ThreadId, Option<String>)>,
+        timestamp: Option<chrono::DateTime<chrono::Local>>,
+    ) -> Self {
+        Self {
+            thread_fingerprint,
+            timestamp,
+            timestamp_text: None,
+            utc: false,
+        }
+    }
 }
 
 impl Display for GLoggerOptionalInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let formatted_fingerprint = match &self.thread_fingerprint {
-            Some((_, Some(name))) => format!("[{}]", name.to_string()),
+            Some((_, Some(name))) => format!("[{}]", name),
             Some((id, None)) => format!("[id: {:?}]", id),
             None => "".to_string(),
         };
-        let timestamp = match &self.timestamp {
-            Some(time) => format!("@{:?}", time),
-            None => "".to_string(),
+        let timestamp = match (&self.timestamp_text, &self.timestamp) {
+            (Some(text), _) => format!("@{}", text),
+            (None, Some(time)) => format!(
+                "@{}",
+                time.to_rfc3339_opts(chrono::SecondsFormat::Millis, false)
+            ),
+            (None, None) => "".to_string(),
         };
         write!(f, "{}{}", formatted_fingerprint, timestamp)
     }
 }
 impl Log for GLogger {
-    fn enabled(&self, _: &log::Metadata) -> bool {
-        //_ is better than allow()
-        true
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.filter().enabled(metadata)
     }
 
+    ///never panics. Records logged after `end` or after the writer thread is gone are handled as
+    ///set by `GLoggerOptions::after_shutdown`.
     fn log(&self, record: &Record) {
-        let log_message = record.args().to_string(); //to_string here so we own the referenced
-        let log_level = record.level();
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let questions = &self.enabled;
         let info = GLoggerOptionalInfo {
-            thread_fingerprint: if let Some(_) = &self
-                .enabled
-                .get()
-                .expect("tried to log on a not set-up logger")
-                .thread_fingerprint
-            {
+            thread_fingerprint: if questions.thread_fingerprint.is_some() {
                 Some((
                     std::thread::current().id(),
-                    match std::thread::current().name() {
-                        None => None,
-                        Some(name) => Some(name.to_owned()),
-                    },
+                    std::thread::current().name().map(|name| name.to_owned()),
                 ))
             } else {
                 None
             },
-            timestamp: if let Some(_) = &self
-                .enabled
-                .get()
-                .expect("tried to log on a not set-up logger")
-                .timestamp
-            {
+            timestamp: if questions.timestamp.is_some() {
                 Some(chrono::Local::now())
             } else {
                 None
             },
+            timestamp_text: None,
+            utc: false,
         };
-        if let Err(error) = self
-            .channel
-            .get()
-            .expect("tried to log a message to a log channel but the log channel was not set up")
-            .send(Ok((log_message, log_level, info)))
-        {
-            let (log_message, log_level, _) = error.0.clone().unwrap();
-            panic!(
-                "failed to send log {:?} at level {:?} due to {}",
-                log_message, log_level, error
-            )
+        let record = GRecord::from_record(record, info, questions.location.is_some());
+        if self.stopped() {
+            return self.after_shutdown(record);
+        }
+        if let Err(message) = self.channel.send(Ok(record)) {
+            if let Ok(record) = *message {
+                self.after_shutdown(record)
+            }
         }
     }
+    ///blocks until the writer thread has handled every message logged before the call, or
+    ///`GLoggerOptions::flush_timeout` passes.
     fn flush(&self) {
-        if let Err(error) = self
-            .channel
-            .get()
-            .expect("tried to log a message to a logger but the log channel was not set up")
-            .send(Err(GLoggerSignal::Flush))
-        {
-            panic!("failed to send flush instruction due to {}", error)
+        let (ack, acknowledged) = channel();
+        if self.channel.send(Err(GLoggerSignal::Flush(ack))).is_ok() {
+            let _ = acknowledged.recv_timeout(self.flush_timeout);
+        }
+    }
+}
+///What `log` calls. It is set once and hands records to the `GLogger` set up last, which stays
+///there after it ends to handle late records as set by `GLoggerOptions::after_shutdown`.
+struct Dispatch(RwLock<Option<Arc<GLogger>>>);
+static DISPATCH: Dispatch = Dispatch(RwLock::new(None));
+impl Dispatch {
+    //a clone, so a flush waiting on the writer thread never holds up a reload
+    fn current(&self) -> Option<Arc<GLogger>> {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+    fn replace(&self, logger: Arc<GLogger>) {
+        log::set_max_level(logger.filter().max_level());
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = Some(logger);
+    }
+}
+///installed once, by the first logger asking for it. Panics are handled by the running logger if
+///it set `GLoggerOptions::panic_hook`, and by the hook from before otherwise.
+fn install_panic_hook() {
+    static INSTALLED: std::sync::Once = std::sync::Once::new();
+    INSTALLED.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            match DISPATCH
+                .current()
+                .filter(|logger| logger.panic_hook && !logger.stopped())
+            {
+                Some(logger) => logger.crash(info.location(), info.payload()),
+                None => previous(info),
+            }
+        }));
+    });
+}
+///closes the thread handling signals for `GLoggerOptions::on_signal`.
+#[cfg(feature = "signals")]
+static SIGNALS: Mutex<Option<signal_hook::iterator::Handle>> = Mutex::new(None);
+///installed by the first logger asking for it, until no running logger does. SIGINT and SIGTERM
+///end the running logger, and are then handled as it set in `GLoggerOptions::on_signal`, or with
+///their default action.
+#[cfg(feature = "signals")]
+fn install_signal_handler() -> Result<(), GlugError> {
+    use signal_hook::consts::{SIGINT, SIGTERM};
+    use signal_hook::low_level::emulate_default_handler;
+    let mut handle = lock(&SIGNALS);
+    if handle.is_some() {
+        return Ok(());
+    }
+    let mut signals =
+        signal_hook::iterator::Signals::new([SIGINT, SIGTERM]).map_err(GlugError::Signals)?;
+    let closer = signals.handle();
+    thread::Builder::new()
+        .name("glug signals".to_string())
+        .spawn(move || {
+            //ends once closed, and dropping `signals` unregisters them
+            for signal in signals.forever() {
+                let on_signal = DISPATCH.current().and_then(|logger| {
+                    let on_signal = logger.on_signal.clone()?;
+                    if !logger.stopped() {
+                        logger.stop_and_wait();
+                    }
+                    Some(on_signal)
+                });
+                match on_signal {
+                    Some(options::OnSignal::Call(callback)) => callback(signal),
+                    _ => {
+                        let _ = emulate_default_handler(signal);
+                    }
+                }
+            }
+        })
+        .map_err(GlugError::Signals)?;
+    *handle = Some(closer);
+    Ok(())
+}
+///stops handling SIGINT and SIGTERM, once no running logger asks for it.
+#[cfg(feature = "signals")]
+fn remove_signal_handler() {
+    if let Some(handle) = lock(&SIGNALS).take() {
+        handle.close();
+    }
+}
+impl Log for Dispatch {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.current()
+            .is_some_and(|logger| logger.enabled(metadata))
+    }
+    //holds the lock while sending, so a reload swaps loggers only once sends to the old one are
+    //done, and none of them get in after it is stopped
+    fn log(&self, record: &Record) {
+        if let Some(logger) = &*self.0.read().unwrap_or_else(|e| e.into_inner()) {
+            logger.log(record)
+        }
+    }
+    fn flush(&self) {
+        if let Some(logger) = self.current() {
+            logger.flush()
         }
     }
 }
+struct Running {
+    logger: Arc<GLogger>,
+    ///`None` once the `GLoggerRef` is dropped.
+    handle: Option<JoinHandle<()>>,
+}
+///Owns the running logger. Dropping it stops the writer thread and waits for it, after which glug
+///can be set up again.
 pub struct GLoggerRef {
-    pub handle: Option<JoinHandle<()>>,
-    pub logger: &'static GLogger,
+    running: Arc<Mutex<Running>>,
+}
+impl GLoggerRef {
+    ///the running logger.
+    pub fn logger(&self) -> Arc<GLogger> {
+        lock(&self.running).logger.clone()
+    }
+    ///a handle to swap the options while running, from any thread.
+    pub fn reload_handle(&self) -> ReloadHandle {
+        ReloadHandle {
+            running: Arc::downgrade(&self.running),
+        }
+    }
+    ///see `ReloadHandle::reload`.
+    pub fn reload(&self, options: GLoggerOptions<ThreadId>) -> Result<(), GlugError> {
+        self.reload_handle().reload(options)
+    }
 }
 impl Drop for GLoggerRef {
     fn drop(&mut self) {
-        self.logger.end();
-        let mut handle = None;
-        swap(&mut handle, &mut self.handle);
-        handle.map(|h| h.join());
+        let mut running = lock(&self.running);
+        running.logger.end();
+        running.handle.take().map(|h| h.join());
+        //unless another logger was set up since, which may ask for signals itself. Held so none
+        //can be until the handler is removed
+        #[cfg(feature = "signals")]
+        {
+            let current = DISPATCH.0.write().unwrap_or_else(|e| e.into_inner());
+            if current
+                .as_ref()
+                .is_some_and(|current| Arc::ptr_eq(current, &running.logger))
+            {
+                remove_signal_handler();
+            }
+        }
+    }
+}
+///Swaps the options of a running logger. Made by `GLoggerRef::reload_handle`.
+#[derive(Clone)]
+pub struct ReloadHandle {
+    running: Weak<Mutex<Running>>,
+}
+impl ReloadHandle {
+    ///sets up a logger with `options` and hands it every record logged from then on, then stops
+    ///the old one once it has written what it was given.
+    ///
+    ///The new logger opens its files only after the old one is done with them, so records stay in
+    ///order and rotation isn't done by two writers at once. Until then, records wait in its queue.
+    ///Files are opened for appending, so what was written before the reload is kept. If they can't
+    ///be opened the old logger keeps running. If one can't be opened again once the old logger
+    ///stopped, the new one runs without it and the error is returned.
+    ///# Examples
+    ///```
+    ///let gref = glug::GLogger::setup();
+    ///let reload = gref.reload_handle();
+    ///reload
+    ///    .reload(glug::GLoggerOptions {
+    ///        filter: "warn".parse().unwrap(),
+    ///        ..Default::default()
+    ///    })
+    ///    .unwrap();
+    ///log::info!("not logged");
+    ///```
+    pub fn reload(&self, options: GLoggerOptions<ThreadId>) -> Result<(), GlugError> {
+        let running = self.running.upgrade().ok_or(GlugError::Dropped)?;
+        let mut running = lock(&running);
+        if running.handle.is_none() {
+            return Err(GlugError::Dropped);
+        }
+        let output = GLogger::output(&options)?;
+        GLogger::check_features(&options)?;
+        //fails while the old logger still runs. Nothing is written through these
+        drop(GLogger::open_sinks(&options, true)?);
+        let sinks = GLogger::sinks(&options, true);
+        //the old writer is handed over once the new one started, which waits for it to end
+        let (give_old, old_writer) = channel::<JoinHandle<()>>();
+        let (opened, open_error) = channel();
+        let open = move || {
+            if let Ok(handle) = old_writer.recv() {
+                let _ = handle.join();
+            }
+            let mut writers = vec![];
+            let mut error = None;
+            for sink in sinks {
+                match GLogger::open_sink(sink) {
+                    Ok(writer) => writers.push(writer),
+                    Err(e) => {
+                        error.get_or_insert(e);
+                    }
+                }
+            }
+            let _ = opened.send(error);
+            writers
+        };
+        let (logger, handle) = GLogger::start(options, output, open)?;
+        if let Err(e) = logger.install_hooks() {
+            //lets the new writer go on without waiting for the old one, to see the stop
+            drop(give_old);
+            logger.end();
+            let _ = handle.join();
+            return Err(e);
+        }
+        DISPATCH.replace(logger.clone());
+        let old = std::mem::replace(
+            &mut *running,
+            Running {
+                logger,
+                handle: Some(handle),
+            },
+        );
+        old.logger.end();
+        if let Some(handle) = old.handle {
+            let _ = give_old.send(handle);
+        }
+        match open_error.recv() {
+            Ok(Some(e)) => Err(e),
+            _ => Ok(()),
+        }
+    }
+    ///changes which records are logged without restarting the writer thread.
+    pub fn set_filter(&self, filter: Filter) -> Result<(), GlugError> {
+        let running = self.running.upgrade().ok_or(GlugError::Dropped)?;
+        let running = lock(&running);
+        log::set_max_level(filter.max_level());
+        *running
+            .logger
+            .filter
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = filter;
+        Ok(())
+    }
+}
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+///marks a logger's writer thread as finished when dropped at the end of it.
+struct Finished(Weak<GLogger>);
+impl Drop for Finished {
+    fn drop(&mut self) {
+        if let Some(logger) = self.0.upgrade() {
+            *lock(&logger.finished.0) = true;
+            logger.finished.1.notify_all();
+        }
     }
 }
 impl GLogger {
@@ -241,64 +1329,205 @@ impl GLogger {
     ///**The writing thread will only end if told to.**
     ///# Examples
     ///```
-    ///fn main() {
-    ///    let gref = glug::GLogger::setup();
-    ///    log::info!("logged a message");
-    ///}
+    ///let gref = glug::GLogger::setup();
+    ///log::info!("logged a message");
     ///```
     pub fn setup() -> GLoggerRef {
         Self::setup_with_options(GLoggerOptions::<ThreadId>::default())
     }
     ///sets up the logger with options. Interchangable with `GLogger::setup`
+    ///# Panics
+    ///if `try_setup_with_options` would return an error.
     ///# Examples
     ///```
     ///use glug::Ansi8;
-    ///fn main() {
-    ///    let gref = glug::GLogger::setup_with_options(glug::GLoggerOptions { colors:
-    ///    [Ansi8::Red,Ansi8::Yellow,Ansi8::Cyan,Ansi8::Magenta,Ansi8::Blue], ..Default::default()});
-    ///    log::info!("logged a message");
-    ///}
+    ///let gref = glug::GLogger::setup_with_options(glug::GLoggerOptions { colors:
+    ///[Ansi8::Red,Ansi8::Yellow,Ansi8::Cyan,Ansi8::Magenta,Ansi8::Blue], ..Default::default()});
+    ///log::info!("logged a message");
     ///```
     pub fn setup_with_options(options: GLoggerOptions<ThreadId>) -> GLoggerRef {
-        eprint!(clear_screen!());
-        static LOGGER: GLogger = GLogger {
-            channel: OnceLock::new(),
-            enabled: OnceLock::new(),
-        };
-        set_logger(&LOGGER).expect("[glug] tried to set up logger twice");
-        log::set_max_level(log::LevelFilter::Trace);
-        let (sender, receiver) = channel();
-        LOGGER
-            .channel
-            .set(sender)
-            .expect("[glug] tried to set up logger twice");
-        LOGGER.enabled.set((&options).into()).unwrap();
+        Self::try_setup_with_options(options).unwrap_or_else(|e| panic!("[glug] {}", e))
+    }
+    ///sets up the logger with options, returning an error instead of panicking if it can't be.
+    ///If another logger was set, or there's no terminal for `options::Output::Terminal`, nothing is
+    ///touched and another logger can be used. Otherwise glug is set as the `log` logger before
+    ///any file is opened, and the panic hook and signal handler are only installed once the
+    ///writer thread started.
+    ///
+    ///glug can be set up again once the last `GLoggerRef` is dropped or the logger is ended. Setup
+    ///then waits for the old writer thread to finish writing, since it may use the same files.
+    ///# Examples
+    ///```
+    ///let options = glug::GLoggerOptions::<std::thread::ThreadId> {
+    ///    save_to_file: Some("/dev/null/glug.log".to_string()),
+    ///    ..Default::default()
+    ///};
+    ///assert!(matches!(
+    ///    glug::GLogger::try_setup_with_options(options),
+    ///    Err(glug::GlugError::FileOpen { .. })
+    ///));
+    ///```
+    pub fn try_setup_with_options(
+        options: GLoggerOptions<ThreadId>,
+    ) -> Result<GLoggerRef, GlugError> {
+        static INSTALLED: OnceLock<()> = OnceLock::new();
+        //an ended logger's writer may still be writing to the files about to be opened
+        if let Some(logger) = DISPATCH.current().filter(|logger| logger.stopped()) {
+            logger.wait_finished();
+        }
+        //held until the new logger is in place, so two setups can't both get past the check
+        let mut current = DISPATCH.0.write().unwrap_or_else(|e| e.into_inner());
+        if current.as_ref().is_some_and(|logger| !logger.finished()) {
+            return Err(GlugError::ExistingLogger);
+        }
+        let output = Self::output(&options)?;
+        Self::check_features(&options)?;
+        //set before any file is opened, so a logger set by someone else leaves them untouched
+        if INSTALLED.get().is_none() {
+            set_logger(&DISPATCH).map_err(|_| GlugError::ExistingLogger)?;
+            let _ = INSTALLED.set(());
+        }
+        let opened = Self::open_sinks(&options, false)?;
+        let (logger, handle) = Self::start(options, output, move || opened)?;
+        if let Err(e) = logger.install_hooks() {
+            logger.end();
+            let _ = handle.join();
+            return Err(e);
+        }
+        log::set_max_level(logger.filter().max_level());
+        *current = Some(logger.clone());
+        Ok(GLoggerRef {
+            running: Arc::new(Mutex::new(Running {
+                logger,
+                handle: Some(handle),
+            })),
+        })
+    }
+    ///fails if the options ask for something glug was built without.
+    fn check_features(options: &GLoggerOptions<ThreadId>) -> Result<(), GlugError> {
+        if cfg!(not(feature = "signals")) && options.on_signal.is_some() {
+            return Err(GlugError::Signals(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "glug was built without the `signals` feature",
+            )));
+        }
+        Ok(())
+    }
+    ///installs the signal handler and panic hook the options asked for, once nothing else can fail.
+    fn install_hooks(&self) -> Result<(), GlugError> {
+        #[cfg(feature = "signals")]
+        if self.on_signal.is_some() {
+            install_signal_handler()?;
+        } else {
+            remove_signal_handler();
+        }
+        if self.panic_hook {
+            install_panic_hook();
+        }
+        Ok(())
+    }
+    ///opens `save_to_file` and the sinks, for appending if `reload`.
+    fn open_sinks(options: &GLoggerOptions<ThreadId>, reload: bool) -> Result<Opened, GlugError> {
+        Self::sinks(options, reload)
+            .into_iter()
+            .map(Self::open_sink)
+            .collect()
+    }
+    ///`save_to_file` and the sinks, with files appended to if `reload`.
+    fn sinks(options: &GLoggerOptions<ThreadId>, reload: bool) -> Vec<options::Sink> {
+        let mut sinks = options.sinks.clone();
+        if let Some(path) = &options.save_to_file {
+            sinks.insert(
+                0,
+                options::Sink {
+                    target: options::SinkTarget::File {
+                        path: path.clone(),
+                        rotation: options.rotation.clone(),
+                        options: options.file_options.clone(),
+                    },
+                    level: log::LevelFilter::Trace,
+                    format: options.file_format.clone(),
+                },
+            );
+        }
+        for sink in &mut sinks {
+            if let options::SinkTarget::File { options, .. } = &mut sink.target {
+                options.append |= reload;
+            }
+        }
+        sinks
+    }
+    ///opens one of them, with its path in the error.
+    fn open_sink(sink: options::Sink) -> Result<(Box<dyn Write + Send>, options::Sink), GlugError> {
+        let writer = sink.target.open().map_err(|source| GlugError::FileOpen {
+            //only files can fail to open
+            path: match &sink.target {
+                options::SinkTarget::File { path, .. } => path.into(),
+                options::SinkTarget::Writer(_) => std::path::PathBuf::new(),
+            },
+            source,
+        })?;
+        Ok((writer, sink))
+    }
+    ///decides what `Output::Auto` means, before anything is opened.
+    fn output(options: &GLoggerOptions<ThreadId>) -> Result<options::Output, GlugError> {
+        let output = options.output.resolve();
+        if output == options::Output::Terminal && termsize::get().is_none() {
+            return Err(GlugError::NoTerminal);
+        }
+        Ok(output)
+    }
+    ///starts a writer thread for the sinks `open` returns, called on that thread.
+    fn start(
+        options: GLoggerOptions<ThreadId>,
+        output: options::Output,
+        open: impl FnOnce() -> Opened + Send + 'static,
+    ) -> Result<(Arc<GLogger>, JoinHandle<()>), GlugError> {
+        let (sender, receiver) = queue::channel(options.queue_capacity, options.overflow);
+        let logger = Arc::new(GLogger {
+            channel: sender,
+            enabled: (&options).into(),
+            filter: RwLock::new(options.filter.clone()),
+            flush_timeout: options.flush_timeout,
+            shutdown_timeout: options.shutdown_timeout,
+            after_shutdown: (options.after_shutdown, options.format.clone()),
+            timestamp_format: options.timestamp_format.clone(),
+            started: chrono::Local::now(),
+            stopped: AtomicBool::new(false),
+            finished: (Mutex::new(false), Condvar::new()),
+            panic_hook: options.panic_hook,
+            #[cfg(feature = "signals")]
+            on_signal: options.on_signal.clone(),
+            terminal: output == options::Output::Terminal,
+            discarded: AtomicUsize::new(0),
+        });
+        let weak = Arc::downgrade(&logger);
+        let finished = Finished(weak.clone());
         let writer_func = move || {
-            let file_writer: Option<Result<Box<dyn std::io::Write>, _>> = match options.save_to_file
-            {
-                Some(path) => Some(match std::fs::File::create(path) {
-                    Ok(f) => Ok(Box::new(f) as Box<dyn std::io::Write>),
-                    Err(e) => {
-                        log::warn!("[glug] failed to open file due to {}", e);
-                        Err(e)
+            //dropped last, even if the thread panics
+            let _finished = finished;
+            let mut writers: Vec<gstore::Writer> = open()
+                .into_iter()
+                .map(|(writer, sink)| {
+                    let reopened = sink.target.reopened();
+                    gstore::Writer {
+                        writer: Ok(writer),
+                        format: sink.format.format(),
+                        level: sink.level,
+                        name: sink.target.name(),
+                        reopen: Some(Box::new(move || reopened.open().map(|w| w as _))),
+                        errors: 0,
+                        failed_at: None,
+                        report: !matches!(sink.format, options::FileFormat::Json),
                     }
-                }),
-                None => None,
-            };
-            let mut writers: Vec<Result<Box<dyn std::io::Write>, std::io::Error>> = vec![];
-            match file_writer {
-                Some(Ok(f)) => writers.push(Ok(f)),
-                Some(Err(e)) => writers.push(Err(e)),
-                None => (),
-            }
-            let separate_log_counts = options.record_threads.map(|_| {
-                Box::new(|g: GLoggerOptionalInfo| g.thread_fingerprint.map(|f| f.0))
-                    as Box<dyn Fn(GLoggerOptionalInfo) -> Option<_>>
-            });
-            let format = Box::new(|p: (String, Level, GLoggerOptionalInfo)| {
-                format!("{:<6}{} {}", p.1, p.2, p.0)
-            });
-            GWriter {
+                })
+                .collect();
+            let summary = options.record_threads.as_ref().is_some_and(|r| r.summary);
+            let separate_log_counts = options
+                .record_threads
+                .map(|_| Box::new(|record: &GRecord| record.thread_id()) as options::KeyFn<_>);
+            let format = options.format;
+            let acks = GWriter {
                 terminal: options.terminal,
                 channel: receiver,
                 signals: vec![],
@@ -309,61 +1538,238 @@ impl GLogger {
                     height: 0,
                 },
                 max_messages_per_loop: options.max_messages_per_loop,
+                frame_interval: options
+                    .max_fps
+                    .map(|fps| Duration::from_secs(1) / max(fps, 1)),
+                tick: options.tick,
+                output,
+                summary,
+                logger: weak,
+                screen: Screen::new(0, 0),
+                dirty: true,
+                last_draw: None,
+                last_size_check: None,
                 store: GStoreOptions {
                     log_colors: options.colors.map(|c| c as usize),
                     separate_log_counts,
                     writers: &mut writers,
-                    format: &format,
+                    format: &*format,
+                    timestamp_format: options.timestamp_format,
+                    show_key_values: options.show_key_values,
+                    reopen: options.reopen,
+                    on_write_error: options.on_write_error,
+                    flush_policy: options.flush_policy,
                 }
                 .into(),
             }
             .log_loop();
+            //files finish compressing as they are dropped, so a signal doesn't end the process first
+            drop(writers);
+            for ack in acks {
+                let _ = ack.send(());
+            }
         };
-        let t = thread::Builder::new()
+        let handle = thread::Builder::new()
             .name("glug writer".to_string())
-            .spawn(writer_func);
-        GLoggerRef {
-            handle: Some(t.expect("unable to name writer thread `glug writer`")),
-            logger: &LOGGER,
-        }
+            .spawn(writer_func)
+            .map_err(GlugError::ThreadSpawn)?;
+        Ok((logger, handle))
     }
     ///tells the writer to end writing.
     ///# Examples
     ///```
-    ///fn main() {
-    ///    let gref = glug::GLogger::setup();
-    ///    log::info!("logged a message");
-    ///}
+    ///let gref = glug::GLogger::setup();
+    ///log::info!("logged a message");
     ///```
     pub fn end(&self) {
-        if let Err(error) = self
-            .channel
-            .get()
-            .expect("[glug] tried to log a message to a logger but the log channel was not set up")
-            .send(Err(GLoggerSignal::Stop))
-        {
-            panic!("[glug] failed to send flush instruction due to {}", error)
+        self.stop(None);
+    }
+    ///like `end`, and `ack` hears once the writer thread is done. Returns whether it was sent.
+    fn stop(&self, ack: Option<mpsc::Sender<()>>) -> bool {
+        self.stopped.store(true, Ordering::Release);
+        self.channel.send(Err(GLoggerSignal::Stop(ack))).is_ok()
+    }
+    ///ends logging and waits up to `GLoggerOptions::shutdown_timeout` for the writer thread to
+    ///write everything, restore the terminal and close its files.
+    fn stop_and_wait(&self) {
+        let (ack, stopped) = channel();
+        if self.stop(Some(ack)) {
+            match self.shutdown_timeout {
+                Some(timeout) => {
+                    let _ = stopped.recv_timeout(timeout);
+                }
+                None => {
+                    let _ = stopped.recv();
+                }
+            }
+        }
+    }
+    ///logs the panic, waits for the writer thread to draw it and restore the terminal, then writes
+    ///the panic with a backtrace below the last frame.
+    fn crash(&self, location: Option<&std::panic::Location>, payload: &(dyn std::any::Any + Send)) {
+        let thread = thread::current();
+        let name = thread.name().unwrap_or("<unnamed>");
+        let report = format!(
+            "thread '{}' panicked at {}:\n{}",
+            name,
+            location
+                .map(|location| location.to_string())
+                .unwrap_or_default(),
+            payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("Box<dyn Any>"),
+        );
+        self.log(
+            &Record::builder()
+                .args(format_args!("{}", report))
+                .level(Level::Error)
+                .target("panic")
+                .file(location.map(|location| location.file()))
+                .line(location.map(|location| location.line()))
+                .build(),
+        );
+        let mut stderr = std::io::stderr().lock();
+        //the writer thread can't wait for itself, so the terminal is restored here
+        if self.channel.receiving() {
+            self.stopped.store(true, Ordering::Release);
+            if self.terminal {
+                let _ = write!(stderr, "{}{}", color!(0), macurses::show_cursor!());
+            }
+        } else {
+            drop(stderr);
+            self.stop_and_wait();
+            stderr = std::io::stderr().lock();
+        }
+        if self.terminal {
+            let _ = writeln!(stderr);
+        }
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        let _ = writeln!(stderr, "{}\nstack backtrace:\n{}", report, backtrace);
+    }
+    fn stopped(&self) -> bool {
+        self.stopped.load(Ordering::Acquire)
+    }
+    fn finished(&self) -> bool {
+        *lock(&self.finished.0)
+    }
+    fn wait_finished(&self) {
+        let mut finished = lock(&self.finished.0);
+        while !*finished {
+            finished = self
+                .finished
+                .1
+                .wait(finished)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+    fn filter(&self) -> std::sync::RwLockReadGuard<'_, Filter> {
+        self.filter.read().unwrap_or_else(|e| e.into_inner())
+    }
+    ///how many records were thrown away because the writer thread was gone, see
+    ///`options::AfterShutdown`.
+    pub fn discarded(&self) -> usize {
+        self.discarded.load(Ordering::Relaxed)
+    }
+    fn after_shutdown(&self, mut record: GRecord) {
+        record.stamp(&self.timestamp_format, &self.started, None);
+        match &self.after_shutdown {
+            (options::AfterShutdown::Stderr, format) => {
+                let _ = writeln!(std::io::stderr().lock(), "{}", format(&record));
+            }
+            (options::AfterShutdown::Discard, _) => {
+                self.discarded.fetch_add(1, Ordering::Relaxed);
+            }
         }
     }
 }
 pub mod gstore {
-    use super::{options::GStoreOptions, GLoggerOptionalInfo};
-    use log::Level;
+    use super::{
+        options::{
+            FlushPolicy, Format, GStoreOptions, KeyFn, OnWriteError, Reopen, TimestampFormat,
+        },
+        GRecord,
+    };
+    use chrono::{DateTime, Local};
+    use log::LevelFilter;
     use std::{
         collections::{HashMap, VecDeque},
+        fmt::Debug,
         hash::Hash,
         io::Write,
+        time::{Duration, Instant},
     };
+    type KeyedCounts<K> = (KeyFn<K>, HashMap<K, [usize; 5]>);
+    ///makes a new writer to replace one that failed.
+    pub type ReopenFn = Box<dyn FnMut() -> std::io::Result<Box<dyn Write>>>;
+    ///somewhere `GStore` writes every record to, like a file.
+    pub struct Writer {
+        ///becomes the error if writing fails, and is then skipped until it is reopened.
+        pub writer: Result<Box<dyn Write>, std::io::Error>,
+        ///how records are written, `None` meaning the store's format.
+        pub format: Option<Format>,
+        ///the least severe level written.
+        pub level: LevelFilter,
+        ///shown with errors, like the path of a file.
+        pub name: String,
+        ///`None` never retries a writer that failed, see `options::Reopen`.
+        pub reopen: Option<ReopenFn>,
+        ///how many times writing, flushing or reopening failed.
+        pub errors: usize,
+        ///when it last failed.
+        pub failed_at: Option<Instant>,
+        ///whether `GStore::write_report` writes to it.
+        pub report: bool,
+    }
+    impl Writer {
+        ///a writer for every record, using the store's format.
+        pub fn new(writer: impl Write + 'static) -> Self {
+            Self {
+                writer: Ok(Box::new(writer)),
+                format: None,
+                level: LevelFilter::Trace,
+                name: String::new(),
+                reopen: None,
+                errors: 0,
+                failed_at: None,
+                report: true,
+            }
+        }
+    }
     pub struct GStore<'a, K: Eq + Hash> {
-        logs: VecDeque<(String, Level, GLoggerOptionalInfo)>,
+        logs: VecDeque<(String, GRecord)>,
         pub counts_total: [usize; 5],
-        pub counts_keyed: Option<(
-            Box<dyn Fn(GLoggerOptionalInfo) -> Option<K>>,
-            HashMap<K, [usize; 5]>,
-        )>,
+        pub counts_keyed: Option<KeyedCounts<K>>,
         pub log_colors: [usize; 5],
-        writers: &'a mut [Result<Box<dyn Write>, std::io::Error>],
-        format: &'a dyn Fn((String, Level, GLoggerOptionalInfo)) -> String,
+        ///where the newest message of each level came from, if locations are recorded.
+        pub last_locations: [Option<String>; 5],
+        ///the newest writer failure, with the writer's name.
+        pub last_write_error: Option<String>,
+        ///how many records were dropped before reaching the store, see `options::Overflow`.
+        pub dropped: usize,
+        ///the most records inserted within a second.
+        pub peak_per_second: usize,
+        ///the first error logged, as formatted.
+        pub first_error: Option<String>,
+        ///the newest error logged, as formatted.
+        pub last_error: Option<String>,
+        ///when the second counted towards `peak_per_second` began, and its records so far.
+        second: (Instant, usize),
+        ///the thread names of keys in `counts_keyed`, where they have one.
+        key_names: HashMap<K, String>,
+        writers: &'a mut [Writer],
+        reopen: Reopen,
+        on_write_error: Option<OnWriteError>,
+        flush_policy: FlushPolicy,
+        ///records written since the writers were last flushed.
+        unflushed: usize,
+        flushed_at: Instant,
+        format: &'a dyn Fn(&GRecord) -> String,
+        timestamp_format: TimestampFormat,
+        started: DateTime<Local>,
+        previous: Option<DateTime<Local>>,
+        show_key_values: bool,
     }
     impl<'a, K: Eq + Hash> From<GStoreOptions<'a, K>> for GStore<'a, K> {
         fn from(value: GStoreOptions<'a, K>) -> Self {
@@ -377,31 +1783,91 @@ pub mod gstore {
                 writers: value.writers,
                 format: value.format,
                 log_colors: value.log_colors,
+                last_locations: Default::default(),
+                last_write_error: None,
+                dropped: 0,
+                peak_per_second: 0,
+                first_error: None,
+                last_error: None,
+                second: (Instant::now(), 0),
+                key_names: HashMap::new(),
+                reopen: value.reopen,
+                on_write_error: value.on_write_error,
+                flush_policy: value.flush_policy,
+                unflushed: 0,
+                flushed_at: Instant::now(),
+                timestamp_format: value.timestamp_format,
+                started: Local::now(),
+                previous: None,
+                show_key_values: value.show_key_values,
             }
         }
     }
     impl<'a, K: Eq + Hash> GStore<'a, K> {
-        pub fn insert(&mut self, log: (String, Level, GLoggerOptionalInfo)) {
-            let (level, info) = (log.1, log.2.clone());
-            let message = (self.format)(log);
-            for writer in &mut *self.writers {
-                if let Ok(w) = writer {
-                    match write!(w, "{}{}", message, '\n') {
-                        Ok(_) => (),
-                        Err(e) => *writer = Err(e),
+        ///formats the record, writes it to every writer that takes its level, and counts it.
+        ///Writers are flushed as set by `FlushPolicy`.
+        pub fn insert(&mut self, mut record: GRecord) {
+            record.stamp(
+                &self.timestamp_format,
+                &self.started,
+                self.previous.as_ref(),
+            );
+            if let Some(time) = record.info.timestamp {
+                self.previous = Some(time);
+            }
+            let level = record.level;
+            let message = (self.format)(&record);
+            self.reopen_failed();
+            for i in 0..self.writers.len() {
+                let writer = &mut self.writers[i];
+                if level > writer.level {
+                    continue;
+                }
+                if let Ok(w) = &mut writer.writer {
+                    let line = match &writer.format {
+                        Some(format) => format(&record) + "\n",
+                        None => format!("{}\n", message),
+                    };
+                    if let Err(e) = w.write_all(line.as_bytes()) {
+                        self.failed(i, e)
                     }
                 }
             }
+            self.unflushed += 1;
+            let policy = &self.flush_policy;
+            if policy.records.is_some_and(|n| self.unflushed >= n)
+                || (policy.on_error && level == log::Level::Error)
+                || self.until_flush().is_some_and(|d| d.is_zero())
+            {
+                self.flush();
+            }
             self.counts_total[level as usize - 1] += 1;
+            if self.second.0.elapsed() >= Duration::from_secs(1) {
+                self.second = (Instant::now(), 0);
+            }
+            self.second.1 += 1;
+            self.peak_per_second = self.peak_per_second.max(self.second.1);
+            if level == log::Level::Error {
+                if self.first_error.is_none() {
+                    self.first_error = Some(message.clone());
+                }
+                self.last_error = Some(message.clone());
+            }
+            if let Some(location) = record.location() {
+                self.last_locations[level as usize - 1] = Some(location);
+            }
             if let Some((get_key, store)) = &mut self.counts_keyed {
-                let key = get_key(info.clone());
+                let key = get_key(&record);
                 match key.map(|key| (*store).get_mut(&key)) {
                     Some(Some(value)) => {
                         value[level as usize - 1] += 1;
                     }
                     Some(None) => {
+                        if let (Some(key), Some(name)) = (get_key(&record), record.thread_name()) {
+                            self.key_names.insert(key, name.to_string());
+                        }
                         store.insert(
-                            get_key(info.clone()).unwrap(),
+                            get_key(&record).unwrap(),
                             [0, 1, 2, 3, 4].map(|i| match i == level as usize - 1 {
                                 true => 1,
                                 false => 0,
@@ -412,76 +1878,355 @@ pub mod gstore {
                 }
             }
             self.logs.truncate(511);
-            self.logs.push_front((message, level, info));
+            let shown = match self.show_key_values || record.key_values.is_empty() {
+                true => message,
+                false => (self.format)(&GRecord {
+                    key_values: vec![],
+                    ..record.clone()
+                }),
+            };
+            self.logs.push_front((shown, record));
+        }
+        ///flushes every writer, disabling the ones that fail like `insert` does.
+        pub fn flush(&mut self) {
+            self.unflushed = 0;
+            self.flushed_at = Instant::now();
+            self.reopen_failed();
+            for i in 0..self.writers.len() {
+                if let Ok(w) = &mut self.writers[i].writer {
+                    if let Err(e) = w.flush() {
+                        self.failed(i, e)
+                    }
+                }
+            }
+        }
+        ///how long until `FlushPolicy::interval` wants written records flushed, if any are
+        ///waiting.
+        pub fn until_flush(&self) -> Option<Duration> {
+            match (self.flush_policy.interval, self.unflushed) {
+                (Some(interval), 1..) => Some(interval.saturating_sub(self.flushed_at.elapsed())),
+                _ => None,
+            }
+        }
+        ///how many times writers failed.
+        pub fn write_errors(&self) -> usize {
+            self.writers.iter().map(|w| w.errors).sum()
+        }
+        ///disables a writer until it is reopened, and tells everyone why.
+        fn failed(&mut self, i: usize, error: std::io::Error) {
+            let writer = &mut self.writers[i];
+            writer.errors += 1;
+            writer.failed_at = Some(Instant::now());
+            if let Some(on_write_error) = &self.on_write_error {
+                on_write_error(&writer.name, &error);
+            }
+            self.last_write_error = Some(match writer.name.is_empty() {
+                true => error.to_string(),
+                false => format!("{}: {}", writer.name, error),
+            });
+            writer.writer = Err(error);
+        }
+        ///replaces the writers that failed long enough ago, as set by `options::Reopen`.
+        fn reopen_failed(&mut self) {
+            let Reopen::After(wait) = self.reopen else {
+                return;
+            };
+            for i in 0..self.writers.len() {
+                let writer = &mut self.writers[i];
+                let due = writer.failed_at.is_some_and(|at| at.elapsed() >= wait);
+                let Some(reopen) = writer
+                    .reopen
+                    .as_mut()
+                    .filter(|_| writer.writer.is_err() && due)
+                else {
+                    continue;
+                };
+                match reopen() {
+                    Ok(w) => {
+                        writer.writer = Ok(w);
+                        writer.failed_at = None;
+                    }
+                    Err(e) => self.failed(i, e),
+                }
+            }
         }
-        pub fn logs(&self) -> &VecDeque<(String, Level, GLoggerOptionalInfo)> {
+        ///the newest logs first, each formatted alongside its record.
+        pub fn logs(&self) -> &VecDeque<(String, GRecord)> {
             &self.logs
         }
+        ///writes `report` to every writer that takes it, see `Writer::report`, then flushes.
+        pub fn write_report(&mut self, report: &str) {
+            for i in 0..self.writers.len() {
+                let writer = &mut self.writers[i];
+                if let (true, Ok(w)) = (writer.report, &mut writer.writer) {
+                    if let Err(e) = w.write_all(report.as_bytes()) {
+                        self.failed(i, e)
+                    }
+                }
+            }
+            self.flush();
+        }
+    }
+    impl<K: Eq + Hash + Debug> GStore<'_, K> {
+        ///the end-of-run report for `RecordThreadsOptions::summary`: how many records were logged
+        ///over how long, the most in a second, totals per level and per key, and the first and
+        ///last error. Keys are shown by thread name if they have one.
+        pub fn report(&self) -> String {
+            const LEVELS: [&str; 5] = ["ERROR", "WARN", "INFO", "DEBUG", "TRACE"];
+            let elapsed = (Local::now() - self.started).to_std().unwrap_or_default();
+            let mut report = format!(
+                "summary: {} records in {:.3}s, peak {} records/s\n",
+                self.counts_total.iter().sum::<usize>(),
+                elapsed.as_secs_f64(),
+                self.peak_per_second,
+            );
+            let levels: Vec<String> = LEVELS
+                .iter()
+                .zip(self.counts_total)
+                .map(|(level, count)| format!("{}: {}", level, count))
+                .collect();
+            report.push_str(&format!("levels: {}\n", levels.join(", ")));
+            if let Some((_, counts)) = &self.counts_keyed {
+                let mut keys: Vec<(String, usize)> = counts
+                    .iter()
+                    .map(|(key, counts)| {
+                        let name = match self.key_names.get(key) {
+                            Some(name) => name.clone(),
+                            None => format!("{:?}", key),
+                        };
+                        (name, counts.iter().sum())
+                    })
+                    .collect();
+                keys.sort();
+                let keys: Vec<String> = keys
+                    .iter()
+                    .map(|(name, count)| format!("{}: {}", name, count))
+                    .collect();
+                report.push_str(&format!("threads: {}\n", keys.join(", ")));
+            }
+            if let Some(error) = &self.first_error {
+                report.push_str(&format!("first error: {}\n", error));
+            }
+            if let Some(error) = &self.last_error {
+                report.push_str(&format!("last error: {}\n", error));
+            }
+            report
+        }
     }
 }
 struct GWriter<'a, K: Eq + Hash> {
     //necessary fields
     terminal: termpin::DivNode<K>,
-    channel: mpsc::Receiver<LogMessage>,
+    channel: queue::Receiver,
     signals: Vec<GLoggerSignal>,
     bound: Box2D<usize>,
+    screen: Screen,
+    dirty: bool,
+    last_draw: Option<Instant>,
+    last_size_check: Option<Instant>,
     //fields for config
     max_messages_per_loop: Option<usize>,
+    frame_interval: Option<Duration>,
+    tick: Option<Duration>,
+    output: options::Output,
+    ///print `GStore::report` when logging ends.
+    summary: bool,
+    ///handles what is left in the queue once the writer ends.
+    logger: Weak<GLogger>,
     store: gstore::GStore<'a, K>,
 }
 
 impl<'a, K: Eq + Hash + Debug> GWriter<'a, K> {
-    fn log_loop(&mut self) {
+    ///runs until stopped, and returns who is waiting to hear that the writer thread is done.
+    fn log_loop(mut self) -> Vec<mpsc::Sender<()>> {
         loop {
             self.read();
-            if let Err(e) = self.draw() {
-                warn!("{}", e)
+            if self.dirty && self.until_frame().is_zero() {
+                self.draw();
+            }
+            if self.store.until_flush().is_some_and(|d| d.is_zero()) {
+                self.store.flush();
             }
-            for signal in &self.signals {
+            for signal in std::mem::take(&mut self.signals) {
                 match signal {
-                    GLoggerSignal::Flush => self.flush(),
-                    GLoggerSignal::Stop => {
+                    GLoggerSignal::Flush(ack) => {
+                        if self.dirty {
+                            self.draw();
+                        }
+                        self.flush();
+                        let _ = ack.send(());
+                    }
+                    GLoggerSignal::Stop(ack) => {
+                        if self.dirty {
+                            self.draw();
+                        }
+                        self.flush();
                         self.max_messages_per_loop = None;
-                        eprint!(
-                            "{}{}{}",
-                            color!(0),
-                            macurses::set_cursor!(self.bound.height, 0),
-                            macurses::show_cursor!()
-                        ); //reset color to gracefully exit
-                        return;
+                        if self.output == options::Output::Terminal {
+                            eprint!(
+                                "{}{}{}",
+                                color!(0),
+                                macurses::set_cursor!(self.bound.height, 1),
+                                macurses::show_cursor!()
+                            ); //reset color to gracefully exit
+                        }
+                        if self.summary {
+                            let report = self.store.report();
+                            if self.output == options::Output::Terminal {
+                                eprintln!();
+                            }
+                            eprint!("{}", report);
+                            self.store.write_report(&report);
+                        }
+                        let mut acks: Vec<_> = ack.into_iter().collect();
+                        //sent after the last read, by threads that hadn't seen `end` yet
+                        for message in self.channel.close() {
+                            match message {
+                                Ok(record) => {
+                                    if let Some(logger) = self.logger.upgrade() {
+                                        logger.after_shutdown(record)
+                                    }
+                                }
+                                Err(GLoggerSignal::Flush(ack))
+                                | Err(GLoggerSignal::Stop(Some(ack))) => acks.push(ack),
+                                Err(GLoggerSignal::Stop(None)) => {}
+                            }
+                        }
+                        return acks;
                     }
                 }
             }
         }
     }
-    fn flush(&self) {
-        todo!()
+    fn flush(&mut self) {
+        self.store.flush();
+        let _ = std::io::stderr().flush();
+    }
+    ///draws the frame into the screen and writes the cells that changed in one go.
+    fn draw(&mut self) {
+        if self.output != options::Output::Terminal {
+            self.dirty = false;
+            return;
+        }
+        self.screen.clear();
+        if let Err(e) = self
+            .terminal
+            .descend(self.bound, &mut self.screen, &self.store)
+        {
+            warn!("{}", e)
+        }
+        let frame = self.screen.diff();
+        let mut stderr = std::io::stderr().lock();
+        let _ = stderr.write_all(frame.as_bytes());
+        let _ = stderr.flush();
+        self.dirty = false;
+        self.last_draw = Some(Instant::now());
     }
-    fn draw(&mut self) -> Result<(), String> {
-        self.terminal.descend(self.bound, &self.store)
+    ///time left before the frame rate cap allows another draw.
+    fn until_frame(&self) -> Duration {
+        match (self.frame_interval, self.last_draw) {
+            (Some(interval), Some(last)) => interval.saturating_sub(last.elapsed()),
+            _ => Duration::ZERO,
+        }
     }
-
-    fn read(&mut self) {
-        self.signals.clear();
-        self.bound = match termsize::get() {
+    ///how long `read` may block before something other than a message needs handling.
+    fn timeout(&self) -> Duration {
+        let until_flush = self.store.until_flush().unwrap_or(Duration::MAX);
+        if self.output != options::Output::Terminal {
+            return until_flush;
+        }
+        let mut timeout = match self.last_size_check {
+            Some(last) => RESIZE_POLL.saturating_sub(last.elapsed()),
+            None => Duration::ZERO,
+        }
+        .min(until_flush);
+        if self.dirty {
+            timeout = timeout.min(self.until_frame());
+        }
+        if let (Some(tick), Some(last)) = (self.tick, self.last_draw) {
+            timeout = timeout.min(tick.saturating_sub(last.elapsed()));
+        }
+        timeout
+    }
+    ///falls back to plain lines if the terminal size can't be found anymore.
+    fn check_size(&mut self) {
+        if self.output != options::Output::Terminal
+            || self
+                .last_size_check
+                .is_some_and(|last| last.elapsed() < RESIZE_POLL)
+        {
+            return;
+        }
+        self.last_size_check = Some(Instant::now());
+        let bound: Box2D<usize> = match termsize::get() {
             Some(size) => size.into(),
             None => {
-                panic!("[glug] could not determine terminal size. Use another terminal or logger")
+                self.output = options::Output::Plain { colors: false };
+                return;
             }
         };
+        if (bound.length, bound.height) != (self.bound.length, self.bound.height) {
+            self.bound = bound;
+            self.screen.resize(bound.length, bound.height);
+            self.dirty = true;
+        }
+    }
+    ///blocks until a message arrives or something else needs doing, then reads up to
+    ///`max_messages_per_loop` messages.
+    fn read(&mut self) {
+        let first = match self.channel.recv_timeout(self.timeout()) {
+            Ok(message) => Some(message),
+            Err(mpsc::RecvTimeoutError::Timeout) => None,
+            Err(mpsc::RecvTimeoutError::Disconnected) => Some(Err(GLoggerSignal::Stop(None))),
+        };
+        self.check_size();
+        if let (Some(tick), Some(last)) = (self.tick, self.last_draw) {
+            if last.elapsed() >= tick {
+                self.dirty = true;
+            }
+        }
+        if self.store.dropped != self.channel.dropped() {
+            self.store.dropped = self.channel.dropped();
+            self.dirty = true;
+        }
         let mut messages_received = 0;
-        while let Ok(message) = self.channel.try_recv() {
+        let mut lines = String::new();
+        let mut next = first;
+        while let Some(message) = next {
             messages_received += 1;
             match message {
                 Ok(log) => {
                     self.store.insert(log);
+                    self.dirty = true;
+                    if let options::Output::Plain { colors } = self.output {
+                        self.plain_line(colors, &mut lines);
+                    }
                 }
                 Err(signal) => self.signals.push(signal),
             }
-            if let Some(max) = self.max_messages_per_loop {
-                if messages_received >= max {
-                    return;
-                }
+            if self
+                .max_messages_per_loop
+                .is_some_and(|max| messages_received >= max)
+            {
+                break;
+            }
+            next = self.channel.try_recv();
+        }
+        if !lines.is_empty() {
+            let _ = std::io::stderr().lock().write_all(lines.as_bytes());
+        }
+    }
+    ///adds the newest log to `lines` as it would be written to a file.
+    fn plain_line(&self, colors: bool, lines: &mut String) {
+        if let Some((message, record)) = self.store.logs().front() {
+            match colors {
+                true => lines.push_str(&format!(
+                    "{}{}{}\n",
+                    color!(self.store.log_colors[record.level as usize - 1]),
+                    message,
+                    color!(0)
+                )),
+                false => lines.push_str(&format!("{}\n", message)),
             }
         }
     }
@@ -0,0 +1,316 @@
+//!log files that are moved aside and started again, see `options::Rotation`.
+use super::options::{Compression, FileOptions, Naming, Period, Rotation};
+use chrono::{DateTime, Local};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+
+///A file for `GLoggerOptions::save_to_file` that rotates when it would grow past
+///`Rotation::max_bytes` or when a new `Rotation::period` begins.
+///
+///Rotation is checked before each write and a write always goes to one file. `GStore` writes each
+///record in one write, so records are never split between files. Writes are buffered until
+///`flush`.
+///
+///Archives are compressed on another thread as set by `Rotation::compression`. Dropping the file
+///waits for it to finish, so every archive is whole once the `GLoggerRef` is dropped.
+///# Examples
+///```
+///use std::io::Write;
+///let dir = std::env::temp_dir().join(format!("glug-doc-rotation-{}", std::process::id()));
+///std::fs::create_dir_all(&dir).unwrap();
+///let rotation = glug::options::Rotation {
+///    max_bytes: Some(8),
+///    ..Default::default()
+///};
+///let mut file =
+///    glug::file::LogFile::open(dir.join("app.log"), rotation, Default::default()).unwrap();
+///file.write_all(b"first\n").unwrap();
+///file.write_all(b"second\n").unwrap();
+///file.flush().unwrap();
+///assert_eq!(std::fs::read_to_string(dir.join("app.log.1")).unwrap(), "first\n");
+///assert_eq!(std::fs::read_to_string(dir.join("app.log")).unwrap(), "second\n");
+///# std::fs::remove_dir_all(&dir).unwrap();
+///```
+pub struct LogFile {
+    path: PathBuf,
+    rotation: Rotation,
+    options: FileOptions,
+    file: Buffered,
+    size: u64,
+    opened: DateTime<Local>,
+    compressing: Option<JoinHandle<io::Result<()>>>,
+}
+impl LogFile {
+    ///opens `path`. What an earlier run left in it is archived first, unless
+    ///`FileOptions::append` is set.
+    pub fn open(
+        path: impl AsRef<Path>,
+        rotation: Rotation,
+        options: FileOptions,
+    ) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut size = 0;
+        let mut opened = Local::now();
+        let mut compressing = None;
+        if let Ok(metadata) = fs::metadata(&path) {
+            if metadata.len() > 0 {
+                let modified = match metadata.modified() {
+                    Ok(time) => time.into(),
+                    Err(_) => Local::now(),
+                };
+                match options.append {
+                    true => (size, opened) = (metadata.len(), modified),
+                    false => {
+                        let archived = archive(&path, &rotation, &modified)?;
+                        compressing = compress_later(archived, rotation.compression);
+                    }
+                }
+            }
+        }
+        Ok(Self {
+            file: Buffered::new(options.open(&path)?, options.fsync),
+            path,
+            rotation,
+            options,
+            size,
+            opened,
+            compressing,
+        })
+    }
+    ///moves the current file aside and starts an empty one.
+    pub fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        self.finish_compressing()?;
+        let archived = archive(&self.path, &self.rotation, &self.opened)?;
+        self.compressing = compress_later(archived, self.rotation.compression);
+        self.file = Buffered::new(self.options.open(&self.path)?, self.options.fsync);
+        self.size = 0;
+        self.opened = Local::now();
+        Ok(())
+    }
+    ///waits for the last archive to be compressed.
+    pub fn finish_compressing(&mut self) -> io::Result<()> {
+        match self.compressing.take().map(JoinHandle::join) {
+            Some(Ok(compressed)) => compressed,
+            Some(Err(_)) => Err(io::Error::other("compressing an archive panicked")),
+            None => Ok(()),
+        }
+    }
+    fn due(&self, len: u64, now: &DateTime<Local>) -> bool {
+        let too_big = self
+            .rotation
+            .max_bytes
+            .is_some_and(|max| self.size > 0 && self.size + len > max);
+        let new_period = self
+            .rotation
+            .period
+            .is_some_and(|period| period.key(&self.opened) != period.key(now));
+        too_big || new_period
+    }
+}
+impl Write for LogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.due(buf.len() as u64, &Local::now()) {
+            self.rotate()?;
+        }
+        self.file.write_all(buf)?;
+        self.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+impl Drop for LogFile {
+    fn drop(&mut self) {
+        let _ = self.finish_compressing();
+    }
+}
+///a buffered file that can sync to disk when flushed.
+pub(crate) struct Buffered {
+    file: BufWriter<File>,
+    fsync: bool,
+}
+impl Buffered {
+    pub(crate) fn new(file: File, fsync: bool) -> Self {
+        Self {
+            file: BufWriter::new(file),
+            fsync,
+        }
+    }
+}
+impl Write for Buffered {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.fsync {
+            self.file.get_ref().sync_data()?;
+        }
+        Ok(())
+    }
+}
+impl Period {
+    ///the part of an archive's name for the period `time` is in.
+    fn key(&self, time: &DateTime<Local>) -> String {
+        match self {
+            Self::Hourly => time.format("%Y-%m-%dT%H").to_string(),
+            Self::Daily => time.format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+///moves `path` to its archive name for logs written from `time`, dropping the oldest archives
+///past `Rotation::keep`. Returns where it was moved, if it was kept.
+fn archive(
+    path: &Path,
+    rotation: &Rotation,
+    time: &DateTime<Local>,
+) -> io::Result<Option<PathBuf>> {
+    let compressed = rotation.compression.extension();
+    match rotation.naming {
+        Naming::Numbered => {
+            let numbered = |n: usize, extension: &str| {
+                let mut name = path.as_os_str().to_os_string();
+                name.push(format!(".{}{}", n, extension));
+                PathBuf::from(name)
+            };
+            if rotation.keep == 0 {
+                fs::remove_file(path)?;
+                return Ok(None);
+            }
+            //archives that failed to compress are shifted along with the rest
+            let mut extensions = vec![""];
+            if !compressed.is_empty() {
+                extensions.push(compressed);
+            }
+            for extension in &extensions {
+                let _ = fs::remove_file(numbered(rotation.keep, extension));
+                for n in (1..rotation.keep).rev() {
+                    if numbered(n, extension).exists() {
+                        fs::rename(numbered(n, extension), numbered(n + 1, extension))?;
+                    }
+                }
+            }
+            fs::rename(path, numbered(1, ""))?;
+            Ok(Some(numbered(1, "")))
+        }
+        Naming::Dated => {
+            let key = rotation.period.unwrap_or(Period::Daily).key(time);
+            let mut archived = dated(path, &key);
+            let mut n = 0;
+            while archived.exists() || with_extension(&archived, compressed).exists() {
+                n += 1;
+                archived = dated(path, &format!("{}.{}", key, n));
+            }
+            fs::rename(path, &archived)?;
+            prune(path, rotation.keep, compressed)?;
+            Ok(Some(archived).filter(|archived| archived.exists()))
+        }
+    }
+}
+///`app.log.1` becomes `app.log.1.gz`.
+fn with_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(extension);
+    PathBuf::from(name)
+}
+///compresses `archived` on another thread, if `compression` isn't `None`.
+fn compress_later(
+    archived: Option<PathBuf>,
+    compression: Compression,
+) -> Option<JoinHandle<io::Result<()>>> {
+    let archived = archived.filter(|_| compression != Compression::None)?;
+    let path = archived.clone();
+    match thread::Builder::new()
+        .name("glug compression".to_string())
+        .spawn(move || compress(&path, compression))
+    {
+        Ok(handle) => Some(handle),
+        //no thread to spare, so compress on this one
+        Err(_) => {
+            let _ = compress(&archived, compression);
+            None
+        }
+    }
+}
+///writes `path` compressed next to it, then removes it. Without a compression feature there is
+///only `Compression::None`, and nothing after the match runs.
+#[cfg_attr(
+    not(any(feature = "gzip", feature = "zstd")),
+    allow(unreachable_code, unused_variables)
+)]
+fn compress(path: &Path, compression: Compression) -> io::Result<()> {
+    let compressed: File = match compression {
+        Compression::None => return Ok(()),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => {
+            let output = File::create(with_extension(path, compression.extension()))?;
+            let mut encoder = flate2::write::GzEncoder::new(output, flate2::Compression::default());
+            io::copy(&mut File::open(path)?, &mut encoder)?;
+            encoder.finish()?
+        }
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => {
+            let output = File::create(with_extension(path, compression.extension()))?;
+            let mut encoder = zstd::Encoder::new(output, 0)?;
+            io::copy(&mut File::open(path)?, &mut encoder)?;
+            encoder.finish()?
+        }
+    };
+    compressed.sync_all()?;
+    fs::remove_file(path)
+}
+///`app.log` becomes `app.{middle}.log`.
+fn dated(path: &Path, middle: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}.{}.{}", stem, middle, extension.to_string_lossy()),
+        None => format!("{}.{}", stem, middle),
+    };
+    path.with_file_name(name)
+}
+///removes all but the newest `keep` dated archives of `path`, compressed ones ending in
+///`compressed`.
+fn prune(path: &Path, keep: usize, compressed: &str) -> io::Result<()> {
+    let stem = format!(
+        "{}.",
+        path.file_stem().unwrap_or_default().to_string_lossy()
+    );
+    let extension = match path.extension() {
+        Some(extension) => format!(".{}", extension.to_string_lossy()),
+        None => String::new(),
+    };
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut archives = vec![];
+    for entry in fs::read_dir(directory)? {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        let uncompressed = match compressed.is_empty() {
+            true => &name[..],
+            false => name.strip_suffix(compressed).unwrap_or(&name),
+        };
+        let middle = uncompressed
+            .strip_prefix(&stem)
+            .and_then(|rest| rest.strip_suffix(&extension));
+        let Some(middle) = middle.filter(|m| m.starts_with(|c: char| c.is_ascii_digit())) else {
+            continue;
+        };
+        //`2026-10-18.10` is newer than `2026-10-18.9`, so the counter is compared as a number
+        let order = match middle.rsplit_once('.') {
+            Some((key, n)) if n.parse::<usize>().is_ok() => (key.to_string(), n.parse().unwrap()),
+            _ => (middle.to_string(), 0),
+        };
+        archives.push((order, name));
+    }
+    archives.sort();
+    let excess = archives.len().saturating_sub(keep);
+    for (_, name) in &archives[..excess] {
+        fs::remove_file(directory.join(name))?;
+    }
+    Ok(())
+}
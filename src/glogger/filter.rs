@@ -0,0 +1,106 @@
+//!per-target level filtering, in the style of `RUST_LOG`.
+use log::{LevelFilter, Metadata};
+use std::str::FromStr;
+
+///A list of directives like `info,my_crate::net=trace,hyper=warn`.
+///
+///A bare level applies to every target, a bare module enables everything for that module, and
+///`module=level` sets the level for a module and its children. The most specific module wins.
+///# Examples
+///```
+///use log::{Level, LevelFilter};
+///let filter = glug::Filter::parse("info,my_crate::net=trace,hyper=warn").unwrap();
+///assert_eq!(filter.max_level(), LevelFilter::Trace);
+///assert!(filter.allows("my_crate::net::tcp", Level::Trace));
+///assert!(!filter.allows("hyper::client", Level::Info));
+///assert!(filter.allows("anything_else", Level::Info));
+///```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filter {
+    //sorted so the longest (most specific) module comes first
+    directives: Vec<(Option<String>, LevelFilter)>,
+}
+impl Filter {
+    ///a filter that lets through everything at `level` or more severe.
+    pub fn new(level: LevelFilter) -> Self {
+        Self {
+            directives: vec![(None, level)],
+        }
+    }
+    ///parses comma separated directives. Empty directives are skipped.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut directives = vec![];
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let mut parts = directive.splitn(2, '=');
+            let (first, second) = (parts.next().unwrap_or("").trim(), parts.next());
+            let parsed = match second {
+                Some(level) => (
+                    Some(first.to_string()),
+                    parse_level(level.trim(), directive)?,
+                ),
+                None => match LevelFilter::from_str(first) {
+                    Ok(level) => (None, level),
+                    Err(_) => (Some(first.to_string()), LevelFilter::Trace),
+                },
+            };
+            if parsed.0.as_deref() == Some("") {
+                return Err(format!("missing module name in directive `{}`", directive));
+            }
+            directives.retain(|d: &(Option<String>, LevelFilter)| d.0 != parsed.0);
+            directives.push(parsed);
+        }
+        directives.sort_by_key(|d| std::cmp::Reverse(d.0.as_ref().map_or(0, |m| m.len() + 1)));
+        Ok(Self { directives })
+    }
+    ///reads directives from an environment variable, returning `None` if it is not set or blank,
+    ///like an exported `RUST_LOG=`.
+    pub fn from_env(var: &str) -> Option<Result<Self, String>> {
+        std::env::var(var)
+            .ok()
+            .filter(|spec| !spec.trim().is_empty())
+            .map(|spec| Self::parse(&spec))
+    }
+    ///the most verbose level any directive lets through. Use for `log::set_max_level`.
+    pub fn max_level(&self) -> LevelFilter {
+        self.directives
+            .iter()
+            .map(|d| d.1)
+            .max()
+            .unwrap_or(LevelFilter::Off)
+    }
+    ///whether a message at `level` from `target` gets through.
+    pub fn allows(&self, target: &str, level: log::Level) -> bool {
+        for (module, filter) in &self.directives {
+            match module {
+                Some(module) if !is_in_module(target, module) => continue,
+                _ => return level <= *filter,
+            }
+        }
+        false
+    }
+    pub fn enabled(&self, metadata: &Metadata) -> bool {
+        self.allows(metadata.target(), metadata.level())
+    }
+}
+impl Default for Filter {
+    ///lets through everything.
+    fn default() -> Self {
+        Self::new(LevelFilter::Trace)
+    }
+}
+impl FromStr for Filter {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+fn parse_level(level: &str, directive: &str) -> Result<LevelFilter, String> {
+    LevelFilter::from_str(level)
+        .map_err(|_| format!("invalid log level `{}` in directive `{}`", level, directive))
+}
+fn is_in_module(target: &str, module: &str) -> bool {
+    match target.strip_prefix(module) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false,
+    }
+}
@@ -0,0 +1,79 @@
+//!JSON Lines: one object per record, for log shippers and `jq`.
+use super::{GRecord, GValue};
+use std::fmt::Write;
+
+///writes a record as a single line JSON object. Fields that weren't recorded are left out.
+pub(crate) fn to_json(record: &GRecord) -> String {
+    let mut out = String::with_capacity(128 + record.message.len());
+    out.push('{');
+    field(&mut out, "level", &string(record.level.as_str()));
+    if let Some(time) = record.timestamp_rfc3339() {
+        field(&mut out, "timestamp", &string(&time));
+    }
+    if let Some(id) = record.thread_id() {
+        //ThreadId has no stable number accessor, but its Debug is `ThreadId(n)`
+        let id: String = format!("{:?}", id)
+            .chars()
+            .filter(char::is_ascii_digit)
+            .collect();
+        field(&mut out, "thread_id", &id);
+    }
+    if let Some(name) = record.thread_name() {
+        field(&mut out, "thread_name", &string(name));
+    }
+    field(&mut out, "target", &string(&record.target));
+    if let Some(module) = &record.module_path {
+        field(&mut out, "module", &string(module));
+    }
+    if let Some(file) = &record.file {
+        field(&mut out, "file", &string(file));
+    }
+    if let Some(line) = record.line {
+        field(&mut out, "line", &line.to_string());
+    }
+    field(&mut out, "message", &string(&record.message));
+    if !record.key_values.is_empty() {
+        let mut kv = String::from("{");
+        for (key, value) in &record.key_values {
+            field(&mut kv, key, &self::value(value));
+        }
+        kv.push('}');
+        field(&mut out, "kv", &kv);
+    }
+    out.push('}');
+    out
+}
+fn field(out: &mut String, key: &str, json: &str) {
+    if !out.ends_with('{') {
+        out.push(',');
+    }
+    out.push_str(&string(key));
+    out.push(':');
+    out.push_str(json);
+}
+fn value(value: &GValue) -> String {
+    match value {
+        GValue::F64(v) if !v.is_finite() => string(&v.to_string()),
+        GValue::Str(v) => string(v),
+        v => v.to_string(),
+    }
+}
+fn string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,188 @@
+//!the queue between `GLogger` and the writer thread. Like `mpsc::channel`, but it can be bounded,
+//!with a choice of what happens when it is full, see `options::Overflow`.
+use super::options::Overflow;
+use super::LogMessage;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+
+struct State {
+    messages: VecDeque<LogMessage>,
+    ///how many of `messages` are records. Signals aren't counted against the capacity, so a full
+    ///queue can always be flushed and stopped.
+    records: usize,
+    senders: usize,
+    receiver: bool,
+}
+struct Shared {
+    state: Mutex<State>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: Option<usize>,
+    overflow: Overflow,
+    dropped: AtomicUsize,
+    ///the thread receiving, which must never wait for itself to make room.
+    receiving: OnceLock<ThreadId>,
+}
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+    fn drop_one(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+}
+pub(crate) struct Sender(Arc<Shared>);
+pub(crate) struct Receiver(Arc<Shared>);
+///a queue holding at most `capacity` records, or any number if `None`. A capacity of 0 could never
+///take a record, so it holds 1.
+pub(crate) fn channel(capacity: Option<usize>, overflow: Overflow) -> (Sender, Receiver) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            messages: VecDeque::new(),
+            records: 0,
+            senders: 1,
+            receiver: true,
+        }),
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+        capacity: capacity.map(|c| c.max(1)),
+        overflow,
+        dropped: AtomicUsize::new(0),
+        receiving: OnceLock::new(),
+    });
+    (Sender(shared.clone()), Receiver(shared))
+}
+impl Sender {
+    ///whether the current thread is the one receiving.
+    pub(crate) fn receiving(&self) -> bool {
+        self.0.receiving.get() == Some(&thread::current().id())
+    }
+    ///queues `message`, or gives it back if the receiver is gone. Records that don't fit are
+    ///handled as set by `Overflow` and counted by `Receiver::dropped`.
+    pub(crate) fn send(&self, message: LogMessage) -> Result<(), Box<LogMessage>> {
+        let shared = &*self.0;
+        let mut state = shared.lock();
+        loop {
+            if !state.receiver {
+                return Err(Box::new(message));
+            }
+            let level = match &message {
+                Ok(record) => record.level,
+                Err(_) => break,
+            };
+            let full = shared.capacity.is_some_and(|c| state.records >= c);
+            if !full {
+                break;
+            }
+            let receiving = self.receiving();
+            match shared.overflow {
+                Overflow::DropOldest => {
+                    if let Some(oldest) = state.messages.iter().position(Result::is_ok) {
+                        state.messages.remove(oldest);
+                        state.records -= 1;
+                        shared.drop_one();
+                    }
+                    break;
+                }
+                Overflow::DropBelow(threshold) if level > threshold => {
+                    shared.drop_one();
+                    return Ok(());
+                }
+                Overflow::DropNewest => {
+                    shared.drop_one();
+                    return Ok(());
+                }
+                //the writer thread logging to itself would wait forever
+                _ if receiving => {
+                    shared.drop_one();
+                    return Ok(());
+                }
+                _ => {
+                    state = shared
+                        .not_full
+                        .wait(state)
+                        .unwrap_or_else(|e| e.into_inner())
+                }
+            }
+        }
+        if message.is_ok() {
+            state.records += 1;
+        }
+        state.messages.push_back(message);
+        shared.not_empty.notify_one();
+        Ok(())
+    }
+}
+impl Drop for Sender {
+    fn drop(&mut self) {
+        self.0.lock().senders -= 1;
+        self.0.not_empty.notify_all();
+    }
+}
+impl Receiver {
+    ///waits up to `timeout` for a message, like `mpsc::Receiver::recv_timeout`.
+    pub(crate) fn recv_timeout(&self, timeout: Duration) -> Result<LogMessage, RecvTimeoutError> {
+        let shared = &*self.0;
+        let _ = shared.receiving.set(thread::current().id());
+        let deadline = Instant::now().checked_add(timeout);
+        let mut state = shared.lock();
+        loop {
+            if let Some(message) = self.pop(&mut state) {
+                return Ok(message);
+            }
+            if state.senders == 0 {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+            state = match deadline {
+                Some(deadline) => {
+                    let wait = deadline.saturating_duration_since(Instant::now());
+                    if wait.is_zero() {
+                        return Err(RecvTimeoutError::Timeout);
+                    }
+                    shared
+                        .not_empty
+                        .wait_timeout(state, wait)
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0
+                }
+                None => shared
+                    .not_empty
+                    .wait(state)
+                    .unwrap_or_else(|e| e.into_inner()),
+            };
+        }
+    }
+    pub(crate) fn try_recv(&self) -> Option<LogMessage> {
+        self.pop(&mut self.0.lock())
+    }
+    ///how many records were dropped because the queue was full.
+    pub(crate) fn dropped(&self) -> usize {
+        self.0.dropped.load(Ordering::Relaxed)
+    }
+    ///stops taking messages, like dropping the receiver, and returns the ones still waiting.
+    pub(crate) fn close(&self) -> Vec<LogMessage> {
+        let mut state = self.0.lock();
+        state.receiver = false;
+        state.records = 0;
+        self.0.not_full.notify_all();
+        state.messages.drain(..).collect()
+    }
+    fn pop(&self, state: &mut State) -> Option<LogMessage> {
+        let message = state.messages.pop_front()?;
+        if message.is_ok() {
+            state.records -= 1;
+            self.0.not_full.notify_one();
+        }
+        Some(message)
+    }
+}
+impl Drop for Receiver {
+    fn drop(&mut self) {
+        self.0.lock().receiver = false;
+        self.0.not_full.notify_all();
+    }
+}
@@ -0,0 +1,219 @@
+//!format templates like `{time:%H:%M:%S%.3f} {level:<5} [{thread}] {target}: {message}`,
+//!parsed once and turned into a formatter.
+use super::{options::Format, GRecord};
+use chrono::format::{Item, StrftimeItems};
+use std::{fmt::Display, sync::Arc};
+
+///A parsed template. Placeholders are `{name}` or `{name:spec}`, and `{{`/`}}` are literal braces.
+///
+///Names are `message`, `level`, `target`, `module`, `file`, `line`, `location` (`file:line`),
+///`thread`, `kv` (`key=value` pairs) and `time`.
+///The spec of `time` is a strftime pattern, in UTC if `TimestampFormat::utc` is set; without one,
+///`time` is written as set by `GLoggerOptions::timestamp_format`. Every other spec is an alignment and width like `<5`, `>8` or
+///`^10`.
+///# Examples
+///```
+///let template = glug::Template::parse("{level:<5} [{target}] {message}").unwrap();
+///let mut record = glug::GRecord::new(log::Level::Warn, "disk almost full");
+///record.target = "storage".to_string();
+///assert_eq!(template.render(&record), "WARN  [storage] disk almost full");
+///assert!(glug::Template::parse("{levle}").is_err());
+///```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Template {
+    pieces: Vec<Piece>,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Piece {
+    Literal(String),
+    Field(Field, Option<Align>),
+    Time(Option<String>),
+}
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Field {
+    Message,
+    Level,
+    Target,
+    Module,
+    File,
+    Line,
+    Location,
+    Thread,
+    KeyValues,
+}
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Align {
+    Left(usize),
+    Right(usize),
+    Center(usize),
+}
+///Why a template failed to parse, and the byte it failed at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TemplateError {
+    pub position: usize,
+    pub reason: String,
+}
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid log format template at byte {}: {}",
+            self.position, self.reason
+        )
+    }
+}
+impl std::error::Error for TemplateError {}
+impl Template {
+    pub fn parse(template: &str) -> Result<Self, TemplateError> {
+        let error = |position, reason: String| Err(TemplateError { position, reason });
+        let mut pieces = vec![];
+        let mut literal = String::new();
+        let mut chars = template.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '{' if chars.peek().map(|c| c.1) == Some('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek().map(|c| c.1) == Some('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => return error(i, "unmatched `}`, use `}}` for a literal brace".into()),
+                '{' => {
+                    let end = match template[i..].find('}') {
+                        Some(end) => i + end,
+                        None => return error(i, "unclosed `{`".into()),
+                    };
+                    let placeholder = &template[i + 1..end];
+                    let (name, spec) = match placeholder.split_once(':') {
+                        Some((name, spec)) => (name.trim(), Some(spec)),
+                        None => (placeholder.trim(), None),
+                    };
+                    if !literal.is_empty() {
+                        pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                    }
+                    pieces.push(match name {
+                        "time" => Piece::Time(match spec {
+                            Some(spec) => {
+                                Some(parse_strftime(spec).map_err(|reason| TemplateError {
+                                    position: i,
+                                    reason,
+                                })?)
+                            }
+                            None => None,
+                        }),
+                        _ => {
+                            let field = match parse_field(name) {
+                                Some(field) => field,
+                                None => return error(i, format!("unknown placeholder `{}`", name)),
+                            };
+                            let align = match spec.map(parse_align) {
+                                Some(Some(align)) => Some(align),
+                                Some(None) => {
+                                    return error(
+                                        i,
+                                        format!("invalid alignment `{}`", spec.unwrap_or("")),
+                                    )
+                                }
+                                None => None,
+                            };
+                            Piece::Field(field, align)
+                        }
+                    });
+                    while chars.peek().is_some_and(|c| c.0 <= end) {
+                        chars.next();
+                    }
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            pieces.push(Piece::Literal(literal));
+        }
+        Ok(Self { pieces })
+    }
+    pub fn render(&self, record: &GRecord) -> String {
+        let mut out = String::new();
+        for piece in &self.pieces {
+            match piece {
+                Piece::Literal(literal) => out.push_str(literal),
+                Piece::Time(pattern) => match (pattern, record.timestamp_text()) {
+                    (None, Some(text)) => out.push_str(text),
+                    (pattern, _) => out.push_str(
+                        &record
+                            .format_timestamp(pattern.as_deref().unwrap_or("%Y-%m-%d %H:%M:%S%.3f"))
+                            .unwrap_or_default(),
+                    ),
+                },
+                Piece::Field(field, align) => {
+                    let value = field_value(*field, record);
+                    out.push_str(&match align {
+                        None => value,
+                        Some(Align::Left(width)) => format!("{:<1$}", value, width),
+                        Some(Align::Right(width)) => format!("{:>1$}", value, width),
+                        Some(Align::Center(width)) => format!("{:^1$}", value, width),
+                    })
+                }
+            }
+        }
+        out
+    }
+    ///a formatter for `GLoggerOptions::format`.
+    pub fn into_format(self) -> Format {
+        Arc::new(move |record| self.render(record))
+    }
+}
+impl std::str::FromStr for Template {
+    type Err = TemplateError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+fn parse_field(name: &str) -> Option<Field> {
+    Some(match name {
+        "message" => Field::Message,
+        "level" => Field::Level,
+        "target" => Field::Target,
+        "module" => Field::Module,
+        "file" => Field::File,
+        "line" => Field::Line,
+        "location" => Field::Location,
+        "thread" => Field::Thread,
+        "kv" => Field::KeyValues,
+        _ => return None,
+    })
+}
+fn parse_align(spec: &str) -> Option<Align> {
+    let mut chars = spec.chars();
+    let align: fn(usize) -> Align = match chars.next()? {
+        '<' => Align::Left,
+        '>' => Align::Right,
+        '^' => Align::Center,
+        _ => return spec.parse().ok().map(Align::Left),
+    };
+    chars.as_str().parse().ok().map(align)
+}
+fn parse_strftime(pattern: &str) -> Result<String, String> {
+    if StrftimeItems::new(pattern).any(|item| item == Item::Error) {
+        return Err(format!("invalid time pattern `{}`", pattern));
+    }
+    Ok(pattern.to_string())
+}
+fn field_value(field: Field, record: &GRecord) -> String {
+    match field {
+        Field::Message => record.message.clone(),
+        Field::Level => record.level.to_string(),
+        Field::Target => record.target.clone(),
+        Field::Module => record.module_path.clone().unwrap_or_default(),
+        Field::File => record.file.clone().unwrap_or_default(),
+        Field::Line => record.line.map(|l| l.to_string()).unwrap_or_default(),
+        Field::Location => record.location().unwrap_or_default(),
+        Field::KeyValues => record.key_values_text(),
+        Field::Thread => match (record.thread_name(), record.thread_id()) {
+            (Some(name), _) => name.to_string(),
+            (None, Some(id)) => format!("{:?}", id),
+            (None, None) => String::new(),
+        },
+    }
+}
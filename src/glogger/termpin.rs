@@ -1,6 +1,8 @@
 //!termpin terminal rationer
 //!divvies up the terminal
 
+pub mod screen;
+use screen::{Canvas, Frame, Screen};
 use std::{
     fmt::Debug,
     hash::Hash,
@@ -9,6 +11,7 @@ use std::{
     sync::Arc,
 };
 type DivLocation = Arc<dyn Fn(usize) -> usize + Send + Sync>;
+type DivElement<K> = Arc<dyn Fn(&mut Canvas<'_>, &super::gstore::GStore<'_, K>) + Send + Sync>;
 #[derive(Debug, Copy, Clone)]
 pub struct Box2D<T> {
     pub x: T,
@@ -25,7 +28,7 @@ impl<T: PartialOrd + Debug + Add<Output = T> + Sub<Output = T> + Copy> Box2D<T>
                 self.height, div
             ));
         }
-        return Ok((
+        Ok((
             Box2D {
                 height: div,
                 ..*self
@@ -35,7 +38,7 @@ impl<T: PartialOrd + Debug + Add<Output = T> + Sub<Output = T> + Copy> Box2D<T>
                 height: self.height - div,
                 ..*self
             },
-        ));
+        ))
     }
     fn div_vert(&self, div: &dyn Fn(T) -> T) -> Result<(Box2D<T>, Box2D<T>), String> {
         let div = div(self.length);
@@ -45,7 +48,7 @@ impl<T: PartialOrd + Debug + Add<Output = T> + Sub<Output = T> + Copy> Box2D<T>
                 self.length, div
             ));
         }
-        return Ok((
+        Ok((
             Box2D {
                 length: div,
                 ..*self
@@ -55,7 +58,7 @@ impl<T: PartialOrd + Debug + Add<Output = T> + Sub<Output = T> + Copy> Box2D<T>
                 length: self.length - div,
                 ..*self
             },
-        ));
+        ))
     }
 }
 pub enum Direction {
@@ -78,33 +81,46 @@ impl From<termsize::Size> for Box2D<usize> {
 pub enum DivNode<K: Eq + Hash> {
     SplitVert(DivLocation, Box<DivNode<K>>, Box<DivNode<K>>),
     SplitHori(DivLocation, Box<DivNode<K>>, Box<DivNode<K>>),
-    Element(Arc<dyn Fn(Box2D<usize>, &super::gstore::GStore<'_, K>) + Send + Sync>),
+    Element(DivElement<K>),
     Empty,
 }
 impl<T: Eq + Hash> DivNode<T> {
+    ///draws every element into its part of `rect` on the screen.
     pub fn descend(
         &mut self,
         rect: Box2D<usize>,
+        screen: &mut Screen,
         store: &super::gstore::GStore<T>,
     ) -> Result<(), String> {
         match self {
             Self::SplitVert(div, left, right) => {
                 let div = rect.div_vert(&**div)?;
-                let r = left.descend(div.0, store);
-                right.descend(div.1, store)?;
+                let r = left.descend(div.0, screen, store);
+                right.descend(div.1, screen, store)?;
                 r?
             }
             Self::SplitHori(div, top, bottom) => {
                 let div = rect.div_hori(&**div)?;
-                let r = top.descend(div.0, store);
-                bottom.descend(div.1, store)?;
+                let r = top.descend(div.0, screen, store);
+                bottom.descend(div.1, screen, store)?;
                 r?
             }
-            Self::Element(disp) => disp(rect, store),
+            Self::Element(disp) => disp(&mut screen.canvas(rect), store),
             Self::Empty => (),
         };
         Ok(())
     }
+    ///draws into a blank screen of a fixed size and captures the result. Needs no terminal.
+    pub fn render(
+        &mut self,
+        length: usize,
+        height: usize,
+        store: &super::gstore::GStore<T>,
+    ) -> Result<Frame, String> {
+        let mut screen = Screen::new(length, height);
+        self.descend(screen.bound(), &mut screen, store)?;
+        Ok(screen.frame())
+    }
     pub fn place(&mut self, other: DivNode<T>, div: (Direction, DivLocation)) {
         use Direction::*;
         use DivNode::*;
@@ -119,23 +135,19 @@ impl<T: Eq + Hash> DivNode<T> {
     }
 }
 pub mod elements {
-    type UBox = super::Box2D<usize>;
     use std::fmt::Debug;
     use std::hash::Hash;
 
     use super::super::gstore::GStore;
-    use super::super::macurses;
-    use macurses::color;
-    use macurses::hide_cursor;
-    use macurses::set_cursor;
-    pub fn draw_logs<K: Eq + Hash>(bound: UBox, store: &GStore<K>) {
-        eprint!("{}{}", color!(0), hide_cursor!());
-        let mut h = (bound.y..bound.y + bound.height).rev().into_iter();
+    use super::screen::{Canvas, Style};
+    pub fn draw_logs<K: Eq + Hash>(canvas: &mut Canvas, store: &GStore<K>) {
+        let bound = canvas.bound();
+        let mut h = (bound.y..bound.y + bound.height).rev();
         for log in store.logs() {
-            eprint!("{}", color!(store.log_colors[log.1 as usize - 1]));
-            for line in nice_lines(&log.0, bound.length) {
+            let style = Style::color(store.log_colors[log.1.level as usize - 1]);
+            for line in nice_lines(&log.0, bound.length).iter().rev() {
                 match h.next() {
-                    Some(h) => eprint!("{}{:<2$}", set_cursor!(h, bound.x), line, bound.length),
+                    Some(h) => canvas.line(bound.x, h, line, style),
                     None => return,
                 }
             }
@@ -143,6 +155,9 @@ pub mod elements {
     }
     fn nice_lines(string: &str, max_len: usize) -> Vec<String> {
         let mut lines = vec![];
+        if max_len == 0 {
+            return lines;
+        }
         string.split('\n').for_each(|line| {
             line.chars()
                 .collect::<Box<[char]>>()
@@ -151,44 +166,34 @@ pub mod elements {
         });
         lines
     }
-    pub fn draw_histogram<K: Eq + Hash>(bound: UBox, store: &GStore<K>) {
-        eprint!("{}{}", color!(0), hide_cursor!());
+    pub fn draw_histogram<K: Eq + Hash>(canvas: &mut Canvas, store: &GStore<K>) {
+        let bound = canvas.bound();
         if bound.length < 6 {
-            for h in bound.y..bound.y + bound.height {
-                eprint!("{}{:<2$}", set_cursor!(h, bound.x), "", bound.length);
-            }
             return;
         }
         for h in 0..bound.height {
-            eprint!("{}", set_cursor!(h + bound.y, bound.x + 2));
             for i in 0..5 {
-                eprint!(
-                    "{}{} ",
-                    if store.counts_total[i] >= bound.height - h {
-                        color!(7)
-                    } else {
-                        color!(27)
-                    },
-                    color!(store.log_colors[i])
-                );
+                let style = Style {
+                    color: store.log_colors[i],
+                    reverse: store.counts_total[i] >= bound.height - h,
+                };
+                canvas.put(bound.x + 1 + i, bound.y + h, ' ', style);
             }
         }
     }
-    pub fn horizontal_bar<K: Eq + Hash>(bound: UBox, _: &GStore<K>) {
-        eprint!("{}{}", color!(0), hide_cursor!());
-        eprint!("{}", set_cursor!(bound.y, bound.x));
-        for _ in bound.x..bound.length {
-            eprint!("=")
+    pub fn horizontal_bar<K: Eq + Hash>(canvas: &mut Canvas, _: &GStore<K>) {
+        let bound = canvas.bound();
+        for x in bound.x..bound.x + bound.length {
+            canvas.put(x, bound.y, '=', Style::default());
         }
     }
-    pub fn vertical_bar<K: Eq + Hash>(bound: UBox, _: &GStore<K>) {
-        eprint!("{}{}", color!(0), hide_cursor!());
-        eprint!("{}", set_cursor!(bound.y, bound.x));
-        for y in bound.y..bound.height {
-            eprint!("{}|", set_cursor!(bound.y + y, bound.x))
+    pub fn vertical_bar<K: Eq + Hash>(canvas: &mut Canvas, _: &GStore<K>) {
+        let bound = canvas.bound();
+        for y in bound.y..bound.y + bound.height {
+            canvas.put(bound.x, y, '|', Style::default());
         }
     }
-    pub fn summary<K: Eq + Hash + Debug>(bound: UBox, store: &GStore<K>) {
+    pub fn summary<K: Eq + Hash + Debug>(canvas: &mut Canvas, store: &GStore<K>) {
         const LOG_LEVEL: [log::Level; 5] = [
             log::Level::Error,
             log::Level::Warn,
@@ -196,15 +201,10 @@ pub mod elements {
             log::Level::Debug,
             log::Level::Trace,
         ];
+        let bound = canvas.bound();
         if bound.height == 0 {
             return;
         }
-        eprint!(
-            "{}{}{}",
-            color!(0),
-            hide_cursor!(),
-            set_cursor!(bound.y, bound.x)
-        );
         let mut line = format!(
             "{:<6}total: {},",
             "",
@@ -219,23 +219,39 @@ pub mod elements {
                 ))
             }
         }
-        eprint!("{:<1$}", line, bound.length);
-        for i in 0..5 {
+        if let Some(time) = store.logs().front().and_then(|log| log.1.timestamp_text()) {
+            line.push_str(&format!(" last: {}", time));
+        }
+        if store.dropped > 0 {
+            line.push_str(&format!(" dropped: {}", store.dropped));
+        }
+        if let Some(error) = &store.last_write_error {
+            line.push_str(&format!(
+                " write errors: {} ({})",
+                store.write_errors(),
+                error
+            ));
+        }
+        canvas.line(bound.x, bound.y, &line, Style::default());
+        for (i, level) in LOG_LEVEL.iter().enumerate() {
             if bound.height <= i + 1 {
                 return;
             }
-            eprint!(
-                "{}{}",
-                color!(store.log_colors[i]),
-                set_cursor!(bound.y + i + 1, bound.x)
-            );
-            let mut line = format!("{:<6}total: {},", LOG_LEVEL[i], store.counts_total[i]);
+            let mut line = format!("{:<6}total: {},", level, store.counts_total[i]);
             if let Some((_, counts)) = &store.counts_keyed {
                 for key in counts.keys() {
                     line.push_str(&format!(" {:?}: {},", key, counts.get(key).unwrap()[i]))
                 }
             }
-            eprint!("{:<1$}", line, bound.length);
+            if let Some(location) = &store.last_locations[i] {
+                line.push_str(&format!(" last at {}", location));
+            }
+            canvas.line(
+                bound.x,
+                bound.y + i + 1,
+                &line,
+                Style::color(store.log_colors[i]),
+            );
         }
     }
 }
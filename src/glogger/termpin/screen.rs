@@ -0,0 +1,223 @@
+//!double buffered screen. Elements draw into a `Canvas`, and only the cells that changed since
+//!the last frame are written to the terminal.
+use super::super::macurses::{clear_screen, color, hide_cursor, set_cursor};
+use super::Box2D;
+
+///how a cell is drawn. `color` is an ANSI foreground code, like the values of `Ansi8`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub color: usize,
+    ///swap foreground and background, used to draw solid blocks.
+    pub reverse: bool,
+}
+impl Style {
+    pub fn color(color: usize) -> Self {
+        Self {
+            color,
+            reverse: false,
+        }
+    }
+    fn escape(&self) -> String {
+        match self.reverse {
+            true => color!(format!("0;{};7", self.color)),
+            false => color!(format!("0;{}", self.color)),
+        }
+    }
+}
+impl Default for Style {
+    fn default() -> Self {
+        Self::color(super::super::Ansi8::Default as usize)
+    }
+}
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub style: Style,
+}
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            ch: ' ',
+            style: Style::default(),
+        }
+    }
+}
+///never drawn by an element, so a front buffer full of it repaints every cell.
+const UNKNOWN: Cell = Cell {
+    ch: '\0',
+    style: Style {
+        color: 0,
+        reverse: false,
+    },
+};
+///the grid elements draw into. `back` is the frame being drawn and `front` is what the terminal
+///is showing.
+pub struct Screen {
+    length: usize,
+    height: usize,
+    front: Vec<Cell>,
+    back: Vec<Cell>,
+    cleared: bool,
+}
+impl Screen {
+    pub fn new(length: usize, height: usize) -> Self {
+        Self {
+            length,
+            height,
+            front: vec![UNKNOWN; length * height],
+            back: vec![Cell::default(); length * height],
+            cleared: false,
+        }
+    }
+    ///changes the size, forcing the next `diff` to repaint everything.
+    pub fn resize(&mut self, length: usize, height: usize) {
+        if (length, height) != (self.length, self.height) {
+            *self = Self::new(length, height);
+        }
+    }
+    pub fn bound(&self) -> Box2D<usize> {
+        Box2D {
+            x: 0,
+            y: 0,
+            length: self.length,
+            height: self.height,
+        }
+    }
+    ///blanks the frame being drawn.
+    pub fn clear(&mut self) {
+        self.back.fill(Cell::default());
+    }
+    ///a canvas that only draws inside of `bound`.
+    pub fn canvas(&mut self, bound: Box2D<usize>) -> Canvas<'_> {
+        Canvas {
+            screen: self,
+            bound,
+        }
+    }
+    pub fn cell(&self, x: usize, y: usize) -> Option<&Cell> {
+        if x >= self.length || y >= self.height {
+            return None;
+        }
+        self.back.get(y * self.length + x)
+    }
+    ///a copy of the frame being drawn, for inspecting without a terminal.
+    pub fn frame(&self) -> Frame {
+        Frame {
+            length: self.length,
+            height: self.height,
+            cells: self.back.clone(),
+        }
+    }
+    ///the escape codes that turn the last frame into this one, batched into one string.
+    pub fn diff(&mut self) -> String {
+        let mut out = String::new();
+        if !self.cleared {
+            out.push_str(clear_screen!());
+            out.push_str(hide_cursor!());
+            self.cleared = true;
+        }
+        let mut cursor = None;
+        let mut style = None;
+        for y in 0..self.height {
+            for x in 0..self.length {
+                let i = y * self.length + x;
+                let cell = self.back[i];
+                if cell == self.front[i] {
+                    continue;
+                }
+                if cursor != Some((x, y)) {
+                    out.push_str(&set_cursor!(y + 1, x + 1));
+                }
+                if style != Some(cell.style) {
+                    out.push_str(&cell.style.escape());
+                    style = Some(cell.style);
+                }
+                out.push(cell.ch);
+                cursor = Some((x + 1, y));
+            }
+        }
+        if style.is_some() {
+            out.push_str(&color!(0));
+        }
+        self.front.copy_from_slice(&self.back);
+        out
+    }
+}
+///part of a `Screen` given to an element. Coordinates are the same as the screen's, and
+///anything outside of `bound` is not drawn.
+pub struct Canvas<'a> {
+    screen: &'a mut Screen,
+    bound: Box2D<usize>,
+}
+impl Canvas<'_> {
+    pub fn bound(&self) -> Box2D<usize> {
+        self.bound
+    }
+    pub fn put(&mut self, x: usize, y: usize, ch: char, style: Style) {
+        let b = self.bound;
+        if x < b.x || y < b.y || x >= b.x + b.length || y >= b.y + b.height {
+            return;
+        }
+        if x >= self.screen.length || y >= self.screen.height {
+            return;
+        }
+        self.screen.back[y * self.screen.length + x] = Cell { ch, style };
+    }
+    ///writes `text` from `x` to the right edge of the bound, padding with spaces.
+    pub fn line(&mut self, x: usize, y: usize, text: &str, style: Style) {
+        let mut chars = text.chars();
+        for x in x..self.bound.x + self.bound.length {
+            self.put(x, y, chars.next().unwrap_or(' '), style);
+        }
+    }
+    pub fn fill(&mut self, ch: char, style: Style) {
+        let b = self.bound;
+        for y in b.y..b.y + b.height {
+            for x in b.x..b.x + b.length {
+                self.put(x, y, ch, style);
+            }
+        }
+    }
+}
+///a captured frame: text plus the style of every cell. Made by `Screen::frame` or
+///`DivNode::render`, so layouts can be checked without a terminal.
+///# Examples
+///```
+///let store: glug::GStore<std::thread::ThreadId> = glug::options::GStoreOptions {
+///    format: &|record: &glug::GRecord| record.message.clone(),
+///    ..Default::default()
+///}
+///.into();
+///let mut terminal = glug::DivNode::Element(std::sync::Arc::new(glug::elements::horizontal_bar));
+///let frame = terminal.render(4, 2, &store).unwrap();
+///assert_eq!(frame.text(), "====\n");
+///```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub length: usize,
+    pub height: usize,
+    ///row by row, `length` cells per row.
+    pub cells: Vec<Cell>,
+}
+impl Frame {
+    pub fn cell(&self, x: usize, y: usize) -> Option<&Cell> {
+        if x >= self.length || y >= self.height {
+            return None;
+        }
+        self.cells.get(y * self.length + x)
+    }
+    ///every row as text, without trailing spaces.
+    pub fn lines(&self) -> Vec<String> {
+        (0..self.height)
+            .map(|y| {
+                let row = &self.cells[y * self.length..(y + 1) * self.length];
+                let line: String = row.iter().map(|c| c.ch).collect();
+                line.trim_end().to_string()
+            })
+            .collect()
+    }
+    ///the rows joined by newlines.
+    pub fn text(&self) -> String {
+        self.lines().join("\n")
+    }
+}
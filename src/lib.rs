@@ -5,17 +5,24 @@
 //!The `glug` logger uses a dedicated writer thread. Panicking may break.
 //!(yes, this example is everywhere in this doc)
 //!```
-//!fn main() {
-//!    let gref = glug::GLogger::setup();
-//!    log::info!("logged a message");
-//!}
+//!let gref = glug::GLogger::setup();
+//!log::info!("logged a message");
 //!```
 mod glogger;
+pub use glogger::file;
+pub use glogger::filter::Filter;
+pub use glogger::gstore;
 pub use glogger::gstore::GStore;
 pub use glogger::options;
+pub use glogger::template::{Template, TemplateError};
 pub use glogger::termpin::elements;
 pub use glogger::termpin::*;
 pub use glogger::Ansi8;
 pub use glogger::GLogger;
+pub use glogger::GLoggerOptionalInfo;
 pub use glogger::GLoggerOptions;
 pub use glogger::GLoggerRef;
+pub use glogger::GRecord;
+pub use glogger::GValue;
+pub use glogger::GlugError;
+pub use glogger::ReloadHandle;
@@ -0,0 +1,26 @@
+mod common;
+use common::TempLog;
+use glug::options::{AfterShutdown, Output};
+use glug::{Filter, GLogger, GLoggerOptions, GRecord};
+use std::sync::Arc;
+
+#[test]
+fn logging_after_end_does_not_panic() {
+    let log = TempLog::new("shutdown");
+    let gref = GLogger::setup_with_options(GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        filter: Filter::default(),
+        after_shutdown: AfterShutdown::Discard,
+        format: Arc::new(|record: &GRecord| record.message.clone()),
+        ..Default::default()
+    });
+    let logger = gref.logger();
+    log::info!("before");
+    drop(gref);
+    log::info!("after");
+    log::logger().flush();
+    logger.end();
+    assert_eq!(logger.discarded(), 1);
+    assert_eq!(log.read(), "before\n");
+}
@@ -0,0 +1,51 @@
+//!helpers for the tests that set up a logger writing to a file.
+#![allow(dead_code)]
+use std::fs;
+use std::path::{Path, PathBuf};
+
+///a log file in the temp directory, removed when dropped so failing tests don't leave it behind.
+pub struct TempLog {
+    path: PathBuf,
+}
+impl TempLog {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("glug-{}-{}.log", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        Self { path }
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    ///for `GLoggerOptions::save_to_file`.
+    pub fn save_to_file(&self) -> Option<String> {
+        Some(self.path.to_string_lossy().into_owned())
+    }
+    pub fn read(&self) -> String {
+        fs::read_to_string(&self.path).unwrap()
+    }
+}
+impl Drop for TempLog {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+///a directory in the temp directory, removed with what's in it when dropped.
+pub struct TempDir {
+    path: PathBuf,
+}
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("glug-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
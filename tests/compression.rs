@@ -0,0 +1,89 @@
+#![cfg(any(feature = "gzip", feature = "zstd"))]
+use glug::file::LogFile;
+use glug::options::{Compression, Naming, Rotation};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+fn directory(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("glug-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+fn names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+fn write_records(path: &Path, rotation: Rotation) {
+    let mut file = LogFile::open(path, rotation, Default::default()).unwrap();
+    for record in ["a\n", "b\n", "c\n", "d\n", "e\n", "f\n", "g\n", "h\n"] {
+        file.write_all(record.as_bytes()).unwrap();
+    }
+    file.flush().unwrap();
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn numbered_archives_are_gzipped_by_the_time_the_file_is_dropped() {
+    let dir = directory("gzip");
+    let rotation = Rotation {
+        max_bytes: Some(4),
+        keep: 2,
+        compression: Compression::Gzip,
+        ..Default::default()
+    };
+    write_records(&dir.join("app.log"), rotation);
+    assert_eq!(names(&dir), ["app.log", "app.log.1.gz", "app.log.2.gz"]);
+    let mut text = String::new();
+    flate2::read::GzDecoder::new(fs::File::open(dir.join("app.log.1.gz")).unwrap())
+        .read_to_string(&mut text)
+        .unwrap();
+    assert_eq!(text, "e\nf\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn compressed_dated_archives_are_pruned() {
+    let dir = directory("gzip-dated");
+    let rotation = Rotation {
+        max_bytes: Some(4),
+        keep: 2,
+        naming: Naming::Dated,
+        compression: Compression::Gzip,
+        ..Default::default()
+    };
+    let today = chrono::Local::now().format("%Y-%m-%d");
+    write_records(&dir.join("app.log"), rotation);
+    assert_eq!(
+        names(&dir),
+        [
+            format!("app.{}.1.log.gz", today),
+            format!("app.{}.2.log.gz", today),
+            "app.log".to_string(),
+        ]
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn numbered_archives_are_zstd_compressed() {
+    let dir = directory("zstd");
+    let rotation = Rotation {
+        max_bytes: Some(4),
+        keep: 1,
+        compression: Compression::Zstd,
+        ..Default::default()
+    };
+    write_records(&dir.join("app.log"), rotation);
+    assert_eq!(names(&dir), ["app.log", "app.log.1.zst"]);
+    let text = zstd::decode_all(fs::File::open(dir.join("app.log.1.zst")).unwrap()).unwrap();
+    assert_eq!(text, b"e\nf\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,37 @@
+use glug::options::Output;
+use glug::{GLogger, GLoggerOptions, GlugError};
+use log::{Log, Metadata, Record};
+
+mod common;
+use common::TempLog;
+
+struct Other;
+impl Log for Other {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+    fn log(&self, _: &Record) {}
+    fn flush(&self) {}
+}
+
+//one test, since the logger can only be set once
+#[test]
+fn failed_setup_leaves_files_and_room_for_another_logger() {
+    if termsize::get().is_none() {
+        let terminal = GLogger::try_setup_with_options(GLoggerOptions {
+            output: Output::Terminal,
+            ..Default::default()
+        });
+        assert!(matches!(terminal, Err(GlugError::NoTerminal)));
+    }
+    let log = TempLog::new("fallible_setup");
+    std::fs::write(log.path(), "yesterday's log\n").unwrap();
+    log::set_logger(&Other).unwrap();
+    let existing = GLogger::try_setup_with_options(GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        ..Default::default()
+    });
+    assert!(matches!(existing, Err(GlugError::ExistingLogger)));
+    assert_eq!(log.read(), "yesterday's log\n");
+}
@@ -0,0 +1,73 @@
+use glug::file::LogFile;
+use glug::options::{FileOptions, Rotation};
+use std::fs;
+use std::io::Write;
+
+#[test]
+fn append_keeps_what_was_there() {
+    let dir = std::env::temp_dir().join(format!("glug-append-{}", std::process::id()));
+    let path = dir.join("nested").join("app.log");
+    let options = FileOptions {
+        append: true,
+        create_dirs: true,
+        mode: None,
+        fsync: false,
+    };
+    options.open(&path).unwrap().write_all(b"one\n").unwrap();
+    options.open(&path).unwrap().write_all(b"two\n").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "one\ntwo\n");
+    let mut truncating = FileOptions::default().open(&path).unwrap();
+    truncating.write_all(b"three\n").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "three\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn missing_directories_are_errors_unless_created() {
+    let dir = std::env::temp_dir().join(format!("glug-dirs-{}", std::process::id()));
+    let path = dir.join("a").join("b.log");
+    assert!(FileOptions::default().open(&path).is_err());
+    let options = FileOptions {
+        create_dirs: true,
+        ..Default::default()
+    };
+    assert!(options.open(&path).is_ok());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn appending_rotated_files_count_what_was_there() {
+    let dir = std::env::temp_dir().join(format!("glug-append-rotate-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("app.log"), "old\n").unwrap();
+    let rotation = Rotation {
+        max_bytes: Some(6),
+        ..Default::default()
+    };
+    let options = FileOptions {
+        append: true,
+        ..Default::default()
+    };
+    let mut file = LogFile::open(dir.join("app.log"), rotation, options).unwrap();
+    file.write_all(b"new\n").unwrap();
+    file.flush().unwrap();
+    assert_eq!(fs::read_to_string(dir.join("app.log.1")).unwrap(), "old\n");
+    assert_eq!(fs::read_to_string(dir.join("app.log")).unwrap(), "new\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn new_files_get_the_mode() {
+    use std::os::unix::fs::PermissionsExt;
+    let path = std::env::temp_dir().join(format!("glug-mode-{}.log", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let options = FileOptions {
+        mode: Some(0o600),
+        ..Default::default()
+    };
+    options.open(&path).unwrap();
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(mode & 0o777, 0o600);
+}
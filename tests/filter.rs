@@ -0,0 +1,43 @@
+mod common;
+use common::TempLog;
+use glug::options::Output;
+use glug::{Filter, GLogger, GLoggerOptions, GRecord};
+use log::{Level, LevelFilter, Metadata};
+use std::sync::Arc;
+
+#[test]
+fn live_logger_applies_the_filter() {
+    let log = TempLog::new("filter");
+    let gref = GLogger::setup_with_options(GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        filter: "warn,net=debug".parse().unwrap(),
+        format: Arc::new(|record: &GRecord| record.message.clone()),
+        ..Default::default()
+    });
+    assert_eq!(log::max_level(), LevelFilter::Debug);
+    let enabled = |level, target| {
+        log::logger().enabled(&Metadata::builder().level(level).target(target).build())
+    };
+    assert!(!enabled(Level::Info, "app"));
+    assert!(enabled(Level::Warn, "app"));
+    assert!(enabled(Level::Debug, "net"));
+    assert!(!enabled(Level::Trace, "net"));
+    log::info!(target: "app", "hidden");
+    log::warn!(target: "app", "shown");
+    log::debug!(target: "net::tcp", "connected");
+    log::trace!(target: "net", "hidden");
+    drop(gref);
+    assert_eq!(log.read(), "shown\nconnected\n");
+}
+
+#[test]
+fn blank_env_var_is_unset() {
+    std::env::set_var("GLUG_TEST_BLANK_FILTER", " ");
+    assert_eq!(Filter::from_env("GLUG_TEST_BLANK_FILTER"), None);
+    std::env::set_var("GLUG_TEST_BLANK_FILTER", "warn");
+    assert_eq!(
+        Filter::from_env("GLUG_TEST_BLANK_FILTER"),
+        Some(Ok(Filter::new(log::LevelFilter::Warn)))
+    );
+}
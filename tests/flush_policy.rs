@@ -0,0 +1,67 @@
+use glug::gstore::Writer;
+use glug::options::{FlushPolicy, GStoreOptions};
+use glug::{GRecord, GStore};
+use log::Level;
+use std::cell::Cell;
+use std::io::Write;
+use std::rc::Rc;
+use std::time::Duration;
+
+#[derive(Clone, Default)]
+struct Flushes(Rc<Cell<usize>>);
+impl Write for Flushes {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.set(self.0.get() + 1);
+        Ok(())
+    }
+}
+
+fn store(writers: &mut [Writer], flush_policy: FlushPolicy) -> GStore<'_, std::thread::ThreadId> {
+    GStoreOptions {
+        writers,
+        flush_policy,
+        ..Default::default()
+    }
+    .into()
+}
+
+#[test]
+fn flushes_after_enough_records_and_errors() {
+    let flushes = Flushes::default();
+    let mut writers = vec![Writer::new(flushes.clone())];
+    let policy = FlushPolicy {
+        records: Some(2),
+        interval: None,
+        on_error: true,
+    };
+    let mut store = store(&mut writers, policy);
+    store.insert(GRecord::new(Level::Info, ""));
+    assert_eq!(flushes.0.get(), 0);
+    store.insert(GRecord::new(Level::Info, ""));
+    assert_eq!(flushes.0.get(), 1);
+    store.insert(GRecord::new(Level::Error, ""));
+    assert_eq!(flushes.0.get(), 2);
+    assert_eq!(store.until_flush(), None);
+}
+
+#[test]
+fn intervals_wait_for_written_records() {
+    let flushes = Flushes::default();
+    let mut writers = vec![Writer::new(flushes.clone())];
+    let policy = FlushPolicy {
+        records: None,
+        interval: Some(Duration::from_secs(3600)),
+        on_error: false,
+    };
+    let mut store = store(&mut writers, policy);
+    assert_eq!(store.until_flush(), None);
+    store.insert(GRecord::new(Level::Error, ""));
+    assert_eq!(flushes.0.get(), 0);
+    assert!(store.until_flush().is_some_and(|d| !d.is_zero()));
+    store.flush();
+    assert_eq!(flushes.0.get(), 1);
+    assert_eq!(store.until_flush(), None);
+}
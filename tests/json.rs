@@ -0,0 +1,78 @@
+use glug::gstore::Writer;
+use glug::options::{FileFormat, GStoreOptions};
+use glug::{GLoggerOptionalInfo, GRecord, GStore, GValue};
+use log::Level;
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+#[derive(Clone, Default)]
+struct Shared(Rc<RefCell<Vec<u8>>>);
+impl Write for Shared {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn records_are_single_line_objects() {
+    let mut record = GRecord::new(Level::Error, "line one\n\"quoted\"\ttab");
+    record.target = "app::db".to_string();
+    record.file = Some("src/db.rs".to_string());
+    record.line = Some(12);
+    record.key_values = vec![
+        ("rows".to_string(), GValue::U64(3)),
+        ("ratio".to_string(), GValue::F64(f64::NAN)),
+        ("path".to_string(), GValue::Str("C:\\tmp".to_string())),
+    ];
+    assert_eq!(
+        glug::options::json_format(&record),
+        r#"{"level":"ERROR","target":"app::db","file":"src/db.rs","line":12,"message":"line one\n\"quoted\"\ttab","kv":{"rows":3,"ratio":"NaN","path":"C:\\tmp"}}"#
+    );
+}
+
+#[test]
+fn timestamps_and_threads_are_included() {
+    let time = chrono::DateTime::parse_from_rfc3339("2026-01-02T03:04:05.678Z")
+        .unwrap()
+        .with_timezone(&chrono::Local);
+    let mut record = GRecord::new(Level::Info, "hi");
+    record.info = GLoggerOptionalInfo::new(
+        Some((std::thread::current().id(), Some("worker".to_string()))),
+        Some(time),
+    );
+    let json = glug::options::json_format(&record);
+    let expected_time = time.to_rfc3339_opts(chrono::SecondsFormat::Millis, false);
+    assert!(json.starts_with(&format!(
+        r#"{{"level":"INFO","timestamp":"{}","thread_id":"#,
+        expected_time
+    )));
+    assert!(json.ends_with(r#","thread_name":"worker","target":"","message":"hi"}"#));
+}
+
+#[test]
+fn json_writers_ignore_the_store_format() {
+    let file = Shared::default();
+    let mut writers = vec![Writer {
+        format: FileFormat::Json.format(),
+        ..Writer::new(file.clone())
+    }];
+    let format = |record: &GRecord| record.message.clone();
+    let mut store: GStore<std::thread::ThreadId> = GStoreOptions {
+        writers: &mut writers,
+        format: &format,
+        ..Default::default()
+    }
+    .into();
+    store.insert(GRecord::new(Level::Warn, "a"));
+    store.insert(GRecord::new(Level::Info, "b"));
+    assert_eq!(store.logs()[0].0, "b");
+    drop(store);
+    assert_eq!(
+        String::from_utf8(file.0.borrow().clone()).unwrap(),
+        "{\"level\":\"WARN\",\"target\":\"\",\"message\":\"a\"}\n{\"level\":\"INFO\",\"target\":\"\",\"message\":\"b\"}\n"
+    );
+}
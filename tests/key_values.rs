@@ -0,0 +1,86 @@
+mod common;
+use common::TempLog;
+use glug::options::{GStoreOptions, Output};
+use glug::{Filter, GLogger, GLoggerOptions, GRecord, GStore, GValue, Template};
+use log::Level;
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Clone, Default)]
+struct Shared(Rc<RefCell<Vec<u8>>>);
+impl Write for Shared {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn record() -> GRecord {
+    let mut record = GRecord::new(Level::Info, "login");
+    record.key_values = vec![
+        ("user_id".to_string(), GValue::I64(42)),
+        ("admin".to_string(), GValue::Bool(false)),
+        ("name".to_string(), GValue::Str("Ada L".to_string())),
+    ];
+    record
+}
+
+#[test]
+fn key_values_render_as_pairs() {
+    assert_eq!(
+        record().key_values_text(),
+        r#"user_id=42 admin=false name="Ada L""#
+    );
+    let template = Template::parse("{message} {kv}").unwrap();
+    assert_eq!(
+        template.render(&record()),
+        r#"login user_id=42 admin=false name="Ada L""#
+    );
+    assert_eq!(
+        glug::options::default_format(&record()),
+        r#"INFO   login user_id=42 admin=false name="Ada L""#
+    );
+}
+
+#[test]
+fn terminal_can_hide_key_values_but_writers_keep_them() {
+    let file = Shared::default();
+    let mut writers = vec![glug::gstore::Writer::new(file.clone())];
+    let format = |record: &GRecord| format!("{} {}", record.message, record.key_values_text());
+    let mut store: GStore<std::thread::ThreadId> = GStoreOptions {
+        writers: &mut writers,
+        format: &format,
+        show_key_values: false,
+        ..Default::default()
+    }
+    .into();
+    store.insert(record());
+    assert_eq!(store.logs()[0].0, "login ");
+    assert_eq!(store.logs()[0].1.key_values.len(), 3);
+    drop(store);
+    assert_eq!(
+        String::from_utf8(file.0.borrow().clone()).unwrap(),
+        "login user_id=42 admin=false name=\"Ada L\"\n"
+    );
+}
+
+#[test]
+fn key_values_are_captured_from_log_macros() {
+    let log = TempLog::new("key-values");
+    let gref = GLogger::setup_with_options(GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        filter: Filter::default(),
+        format: Arc::new(|record: &GRecord| {
+            format!("{} {}", record.message, record.key_values_text())
+        }),
+        ..Default::default()
+    });
+    log::warn!(attempt = 2, user = "ada"; "hello");
+    drop(gref);
+    assert_eq!(log.read(), "hello attempt=2 user=ada\n");
+}
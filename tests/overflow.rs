@@ -0,0 +1,44 @@
+mod common;
+use common::TempLog;
+use glug::options::{Output, Overflow};
+use glug::{Filter, GLogger, GLoggerOptions, GRecord};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+
+#[test]
+fn a_full_queue_drops_the_oldest_records() {
+    let log = TempLog::new("overflow");
+    //the writer thread waits in the formatter while "first" is written, so the queue fills up
+    let gate = Arc::new((Mutex::new(false), Condvar::new()));
+    let (entered, writing) = mpsc::channel();
+    let entered = Mutex::new(entered);
+    let format = {
+        let gate = gate.clone();
+        move |record: &GRecord| {
+            if record.message == "first" {
+                let _ = entered.lock().unwrap().send(());
+                let (open, opened) = &*gate;
+                drop(opened.wait_while(open.lock().unwrap(), |open| !*open));
+            }
+            record.message.clone()
+        }
+    };
+    let gref = GLogger::setup_with_options(GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        filter: Filter::default(),
+        queue_capacity: Some(2),
+        overflow: Overflow::DropOldest,
+        format: Arc::new(format),
+        ..Default::default()
+    });
+    log::info!("first");
+    writing.recv().unwrap();
+    for message in ["a", "b", "c", "d"] {
+        log::info!("{}", message);
+    }
+    *gate.0.lock().unwrap() = true;
+    gate.1.notify_all();
+    log::logger().flush();
+    drop(gref);
+    assert_eq!(log.read(), "first\nc\nd\n");
+}
@@ -0,0 +1,35 @@
+mod common;
+use common::TempLog;
+use glug::options::{AfterShutdown, Output};
+use glug::{Filter, GLogger, GLoggerOptions, GRecord};
+use std::sync::Arc;
+
+#[test]
+fn panics_are_logged_and_stop_the_writer() {
+    let log = TempLog::new("panic");
+    let gref = GLogger::setup_with_options(GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        filter: Filter::default(),
+        after_shutdown: AfterShutdown::Discard,
+        panic_hook: true,
+        format: Arc::new(|record: &GRecord| format!("{} {}", record.level, record.message)),
+        ..Default::default()
+    });
+    log::info!("before");
+    let crashed = std::thread::Builder::new()
+        .name("crasher".to_string())
+        .spawn(|| panic!("boom"))
+        .unwrap()
+        .join();
+    assert!(crashed.is_err());
+    //the writer thread is done, so this is thrown away
+    log::info!("after");
+    assert_eq!(gref.logger().discarded(), 1);
+    drop(gref);
+    let written = log.read();
+    assert!(
+        written.starts_with("INFO before\nERROR thread 'crasher' panicked at tests/panic_hook.rs:")
+    );
+    assert!(written.ends_with(":\nboom\n"));
+}
@@ -0,0 +1,31 @@
+mod common;
+use common::TempLog;
+use glug::options::Output;
+use glug::{Filter, GLogger, GLoggerOptions, GRecord};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+#[test]
+fn zero_capacity_still_takes_records() {
+    let log = TempLog::new("zero-capacity");
+    let gref = GLogger::setup_with_options(GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        filter: Filter::default(),
+        queue_capacity: Some(0),
+        format: Arc::new(|record: &GRecord| record.message.clone()),
+        ..Default::default()
+    });
+    let (done, finished) = mpsc::channel();
+    std::thread::spawn(move || {
+        for message in ["a", "b", "c"] {
+            log::info!("{}", message);
+        }
+        done.send(()).unwrap();
+    });
+    finished
+        .recv_timeout(Duration::from_secs(5))
+        .expect("logging blocked on an empty queue");
+    drop(gref);
+    assert_eq!(log.read(), "a\nb\nc\n");
+}
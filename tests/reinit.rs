@@ -0,0 +1,57 @@
+mod common;
+use common::TempLog;
+use glug::options::Output;
+use glug::{GLogger, GLoggerOptions, GRecord, GlugError};
+use std::sync::Arc;
+
+fn options(log: &TempLog, filter: &str) -> GLoggerOptions<std::thread::ThreadId> {
+    GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        filter: filter.parse().unwrap(),
+        format: Arc::new(|record: &GRecord| record.message.clone()),
+        ..Default::default()
+    }
+}
+
+//one test, since only one logger can run at a time
+#[test]
+fn set_up_again_and_reload() {
+    let first = TempLog::new("reinit-first");
+    let second = TempLog::new("reinit-second");
+    let third = TempLog::new("reinit-third");
+
+    let gref = GLogger::setup_with_options(options(&first, "info"));
+    log::info!("one");
+    drop(gref);
+
+    let gref = GLogger::setup_with_options(options(&second, "info"));
+    assert!(matches!(
+        GLogger::try_setup_with_options(options(&third, "info")),
+        Err(GlugError::ExistingLogger)
+    ));
+    log::info!("two");
+    log::debug!("hidden");
+    let (reload, debug) = (gref.reload_handle(), options(&second, "debug"));
+    std::thread::spawn(move || reload.reload(debug).unwrap())
+        .join()
+        .unwrap();
+    log::debug!("three");
+    gref.reload_handle()
+        .set_filter("warn".parse().unwrap())
+        .unwrap();
+    log::info!("hidden");
+    log::warn!("four");
+    gref.reload(options(&third, "info")).unwrap();
+    log::info!("five");
+    let reload = gref.reload_handle();
+    drop(gref);
+    assert!(matches!(
+        reload.reload(options(&third, "info")),
+        Err(GlugError::Dropped)
+    ));
+
+    assert_eq!(first.read(), "one\n");
+    assert_eq!(second.read(), "two\nthree\nfour\n");
+    assert_eq!(third.read(), "five\n");
+}
@@ -0,0 +1,58 @@
+mod common;
+use common::TempDir;
+use glug::options::{Output, Rotation};
+use glug::{GLogger, GLoggerOptions, GRecord};
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+const RECORDS: usize = 20_000;
+const KEEP: usize = 1000;
+
+#[test]
+fn reloading_keeps_a_rotating_file_in_order() {
+    let dir = TempDir::new("reload-rotation");
+    let path = dir.path().join("app.log");
+    let options = || GLoggerOptions {
+        save_to_file: Some(path.to_string_lossy().into_owned()),
+        rotation: Some(Rotation {
+            max_bytes: Some(1024),
+            keep: KEEP,
+            ..Default::default()
+        }),
+        output: Output::Plain { colors: false },
+        filter: glug::Filter::default(),
+        format: Arc::new(|record: &GRecord| record.message.clone()),
+        ..Default::default()
+    };
+
+    let gref = GLogger::setup_with_options(options());
+    let done = Arc::new(AtomicBool::new(false));
+    let producer = std::thread::spawn({
+        let done = done.clone();
+        move || {
+            for i in 0..RECORDS {
+                log::info!("{}", i);
+            }
+            done.store(true, Ordering::Relaxed);
+        }
+    });
+    let mut reloads = 0;
+    while reloads < 5 || !done.load(Ordering::Relaxed) {
+        gref.reload(options()).unwrap();
+        reloads += 1;
+    }
+    producer.join().unwrap();
+    drop(gref);
+
+    //oldest archive first, then the file itself
+    let mut written = String::new();
+    for n in (1..=KEEP).rev() {
+        if let Ok(archive) = fs::read_to_string(format!("{}.{}", path.display(), n)) {
+            written += &archive;
+        }
+    }
+    written += &fs::read_to_string(&path).unwrap();
+    let expected: String = (0..RECORDS).map(|i| format!("{}\n", i)).collect();
+    assert_eq!(written, expected);
+}
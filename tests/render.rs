@@ -0,0 +1,174 @@
+use glug::elements::*;
+use glug::options::GStoreOptions;
+use glug::screen::{Screen, Style};
+use glug::{DivNode, GRecord, GStore};
+use log::Level;
+use std::sync::Arc;
+use std::thread::ThreadId;
+
+const COLORS: [usize; 5] = [31, 33, 32, 34, 39];
+
+fn store(format: &dyn Fn(&GRecord) -> String) -> GStore<'_, ThreadId> {
+    GStoreOptions {
+        log_colors: COLORS,
+        format,
+        ..Default::default()
+    }
+    .into()
+}
+fn log(store: &mut GStore<ThreadId>, level: Level, message: &str) {
+    store.insert(GRecord::new(level, message));
+}
+
+#[test]
+fn logs_are_drawn_newest_at_the_bottom() {
+    let mut store = store(&|record| record.message.clone());
+    log(&mut store, Level::Info, "first");
+    log(&mut store, Level::Error, "second");
+    let frame = DivNode::Element(Arc::new(draw_logs))
+        .render(10, 3, &store)
+        .unwrap();
+    assert_eq!(frame.lines(), ["", "first", "second"]);
+    assert_eq!(frame.cell(0, 1).unwrap().style, Style::color(32));
+    assert_eq!(frame.cell(0, 2).unwrap().style, Style::color(31));
+}
+
+#[test]
+fn long_and_multiline_logs_wrap_in_order() {
+    let mut store = store(&|record| record.message.clone());
+    log(&mut store, Level::Info, "abcdefgh\nij");
+    let frame = DivNode::Element(Arc::new(draw_logs))
+        .render(5, 4, &store)
+        .unwrap();
+    assert_eq!(frame.text(), "\nabcde\nfgh\nij");
+}
+
+#[test]
+fn logs_that_do_not_fit_are_cut_off_at_the_top() {
+    let mut store = store(&|record| record.message.clone());
+    for i in 0..5 {
+        log(&mut store, Level::Info, &i.to_string());
+    }
+    let frame = DivNode::Element(Arc::new(draw_logs))
+        .render(3, 2, &store)
+        .unwrap();
+    assert_eq!(frame.text(), "3\n4");
+}
+
+#[test]
+fn histogram_fills_columns_by_count() {
+    let mut store = store(&|record| record.message.clone());
+    log(&mut store, Level::Error, "");
+    log(&mut store, Level::Error, "");
+    log(&mut store, Level::Trace, "");
+    let frame = DivNode::Element(Arc::new(draw_histogram))
+        .render(6, 2, &store)
+        .unwrap();
+    let filled = |x, y| frame.cell(x, y).unwrap().style.reverse;
+    assert!(filled(1, 0) && filled(1, 1));
+    assert!(!filled(5, 0) && filled(5, 1));
+    assert!(!filled(2, 1) && !filled(3, 1) && !filled(4, 1));
+    assert_eq!(frame.cell(5, 1).unwrap().style.color, 39);
+}
+
+#[test]
+fn summary_shows_where_each_level_last_came_from() {
+    let mut store = store(&|record| record.message.clone());
+    let mut record = GRecord::new(Level::Error, "");
+    record.file = Some("src/main.rs".to_string());
+    record.line = Some(7);
+    store.insert(record);
+    log(&mut store, Level::Error, "");
+    let frame = DivNode::Element(Arc::new(summary))
+        .render(40, 3, &store)
+        .unwrap();
+    assert_eq!(frame.lines()[1], "ERROR total: 2, last at src/main.rs:7");
+    assert_eq!(frame.lines()[2], "WARN  total: 0,");
+}
+
+#[test]
+fn summary_counts_every_level() {
+    let mut store = store(&|record| record.message.clone());
+    log(&mut store, Level::Warn, "");
+    log(&mut store, Level::Warn, "");
+    log(&mut store, Level::Debug, "");
+    let frame = DivNode::Element(Arc::new(summary))
+        .render(20, 6, &store)
+        .unwrap();
+    assert_eq!(
+        frame.lines(),
+        [
+            "      total: 3,",
+            "ERROR total: 0,",
+            "WARN  total: 2,",
+            "INFO  total: 0,",
+            "DEBUG total: 1,",
+            "TRACE total: 0,",
+        ]
+    );
+    assert_eq!(frame.cell(0, 2).unwrap().style, Style::color(33));
+}
+
+#[test]
+fn default_layout_places_every_element() {
+    let mut store = store(&|record| format!("{:<6}{}", record.level, record.message));
+    log(&mut store, Level::Info, "hello");
+    let frame = glug::GLoggerOptions::<ThreadId>::default()
+        .terminal
+        .render(20, 10, &store)
+        .unwrap();
+    let lines = frame.lines();
+    assert_eq!(lines[2], "INFO  hello  |");
+    assert_eq!(lines[3], "=".repeat(20));
+    assert_eq!(lines[4], "      total: 1,");
+    assert_eq!(lines[7], "INFO  total: 1,");
+    assert!(frame.cell(17, 2).unwrap().style.reverse);
+}
+
+#[test]
+fn bad_layouts_are_errors() {
+    let store = store(&|record| record.message.clone());
+    let mut terminal = DivNode::Element(Arc::new(draw_logs));
+    terminal.place(
+        DivNode::Element(Arc::new(summary)),
+        (glug::Direction::Down, Arc::new(|x| x + 1)),
+    );
+    assert!(terminal.render(10, 4, &store).is_err());
+}
+
+#[test]
+fn only_changed_cells_are_written() {
+    let mut store = store(&|record| record.message.clone());
+    let mut terminal = DivNode::Element(Arc::new(draw_logs));
+    let mut screen = Screen::new(8, 2);
+    log(&mut store, Level::Info, "same");
+    terminal
+        .descend(screen.bound(), &mut screen, &store)
+        .unwrap();
+    assert!(screen.diff().contains("same"));
+    screen.clear();
+    terminal
+        .descend(screen.bound(), &mut screen, &store)
+        .unwrap();
+    assert_eq!(screen.diff(), "");
+    log(&mut store, Level::Info, "samf");
+    screen.clear();
+    terminal
+        .descend(screen.bound(), &mut screen, &store)
+        .unwrap();
+    let diff = screen.diff();
+    assert!(diff.contains("same"));
+    assert!(!diff.contains("samf"));
+    assert!(diff.ends_with("f\x1b[0m"));
+}
+
+#[test]
+fn summary_shows_dropped_records() {
+    let mut store = store(&|record| record.message.clone());
+    log(&mut store, Level::Info, "");
+    store.dropped = 3;
+    let frame = DivNode::Element(Arc::new(summary))
+        .render(30, 1, &store)
+        .unwrap();
+    assert_eq!(frame.lines()[0], "      total: 1, dropped: 3");
+}
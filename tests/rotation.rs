@@ -0,0 +1,105 @@
+use glug::file::LogFile;
+use glug::options::{Naming, Rotation};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+fn directory(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("glug-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+fn names(dir: &PathBuf) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn numbered_archives_shift_and_are_capped() {
+    let dir = directory("numbered");
+    let rotation = Rotation {
+        max_bytes: Some(4),
+        keep: 2,
+        ..Default::default()
+    };
+    let mut file = LogFile::open(dir.join("app.log"), rotation, Default::default()).unwrap();
+    for record in ["a\n", "b\n", "c\n", "d\n", "e\n", "f\n", "g\n", "h\n"] {
+        file.write_all(record.as_bytes()).unwrap();
+    }
+    file.flush().unwrap();
+    assert_eq!(names(&dir), ["app.log", "app.log.1", "app.log.2"]);
+    assert_eq!(fs::read_to_string(dir.join("app.log")).unwrap(), "g\nh\n");
+    assert_eq!(fs::read_to_string(dir.join("app.log.1")).unwrap(), "e\nf\n");
+    assert_eq!(fs::read_to_string(dir.join("app.log.2")).unwrap(), "c\nd\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn records_bigger_than_the_limit_are_not_split() {
+    let dir = directory("whole");
+    let rotation = Rotation {
+        max_bytes: Some(4),
+        ..Default::default()
+    };
+    let mut file = LogFile::open(dir.join("app.log"), rotation, Default::default()).unwrap();
+    file.write_all(b"a long record\n").unwrap();
+    file.write_all(b"another long record\n").unwrap();
+    file.flush().unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.join("app.log.1")).unwrap(),
+        "a long record\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("app.log")).unwrap(),
+        "another long record\n"
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn dated_archives_are_counted_and_pruned() {
+    let dir = directory("dated");
+    let rotation = Rotation {
+        keep: 2,
+        naming: Naming::Dated,
+        ..Default::default()
+    };
+    let today = chrono::Local::now().format("%Y-%m-%d");
+    let mut file = LogFile::open(dir.join("app.log"), rotation, Default::default()).unwrap();
+    for record in ["a\n", "b\n", "c\n"] {
+        file.write_all(record.as_bytes()).unwrap();
+        file.rotate().unwrap();
+    }
+    file.write_all(b"d\n").unwrap();
+    assert_eq!(
+        names(&dir),
+        [
+            format!("app.{}.1.log", today),
+            format!("app.{}.2.log", today),
+            "app.log".to_string(),
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join(format!("app.{}.2.log", today))).unwrap(),
+        "c\n"
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn logs_left_by_an_earlier_run_are_archived() {
+    let dir = directory("reopen");
+    fs::write(dir.join("app.log"), "old\n").unwrap();
+    let mut file =
+        LogFile::open(dir.join("app.log"), Rotation::default(), Default::default()).unwrap();
+    file.write_all(b"new\n").unwrap();
+    file.flush().unwrap();
+    assert_eq!(fs::read_to_string(dir.join("app.log.1")).unwrap(), "old\n");
+    assert_eq!(fs::read_to_string(dir.join("app.log")).unwrap(), "new\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,36 @@
+mod common;
+use common::TempLog;
+use glug::options::Output;
+use glug::{Filter, GLogger, GLoggerOptions, GRecord};
+use std::sync::Arc;
+
+#[test]
+fn custom_format_sees_the_whole_record() {
+    let log = TempLog::new("format");
+    let gref = GLogger::setup_with_options(GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        filter: Filter::default(),
+        format: Arc::new(|record: &GRecord| {
+            format!(
+                "{} {} {}:{} [{}] {}",
+                record.level,
+                record.target,
+                record.file.as_deref().unwrap_or("?"),
+                record.line.is_some(),
+                record.thread_name().unwrap_or("?"),
+                record.message
+            )
+        }),
+        ..Default::default()
+    });
+    std::thread::Builder::new()
+        .name("worker".into())
+        .spawn(|| log::warn!(target: "net", "hello"))
+        .unwrap()
+        .join()
+        .unwrap();
+    log::logger().flush();
+    drop(gref);
+    assert_eq!(log.read(), "WARN net tests/setup.rs:true [worker] hello\n");
+}
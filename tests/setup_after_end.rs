@@ -0,0 +1,28 @@
+mod common;
+use common::TempLog;
+use glug::options::Output;
+use glug::{Filter, GLogger, GLoggerOptions, GRecord};
+use std::sync::Arc;
+
+#[test]
+fn setup_waits_for_the_ended_writer() {
+    let log = TempLog::new("setup-after-end");
+    let options = || GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        filter: Filter::default(),
+        format: Arc::new(|record: &GRecord| record.message.clone()),
+        ..Default::default()
+    };
+    let first = GLogger::setup_with_options(options());
+    for i in 0..20_000 {
+        log::info!("{}", i);
+    }
+    first.logger().end();
+    //truncates the file, which the first writer must be done with
+    let second = GLogger::setup_with_options(options());
+    log::info!("second");
+    drop(second);
+    drop(first);
+    assert_eq!(log.read(), "second\n");
+}
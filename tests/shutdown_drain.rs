@@ -0,0 +1,46 @@
+mod common;
+use common::TempLog;
+use glug::options::{AfterShutdown, Output};
+use glug::{Filter, GLogger, GLoggerOptions, GRecord};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+//records sent while the writer thread ends race with it, so this runs it many times
+#[test]
+fn every_record_is_written_or_discarded_at_shutdown() {
+    let log = TempLog::new("shutdown-drain");
+    for _ in 0..20 {
+        let gref = GLogger::setup_with_options(GLoggerOptions {
+            save_to_file: log.save_to_file(),
+            output: Output::Plain { colors: false },
+            filter: Filter::default(),
+            after_shutdown: AfterShutdown::Discard,
+            //blocked senders wake up and queue their record while the writer thread ends
+            queue_capacity: Some(1),
+            format: Arc::new(|record: &GRecord| record.message.clone()),
+            ..Default::default()
+        });
+        let logger = gref.logger();
+        let done = Arc::new(AtomicBool::new(false));
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let done = done.clone();
+                std::thread::spawn(move || {
+                    let mut sent = 0;
+                    while !done.load(Ordering::Relaxed) {
+                        log::info!("{}", sent);
+                        sent += 1;
+                    }
+                    sent
+                })
+            })
+            .collect();
+        std::thread::sleep(Duration::from_millis(2));
+        drop(gref);
+        done.store(true, Ordering::Relaxed);
+        let sent: usize = threads.into_iter().map(|t| t.join().unwrap()).sum();
+        let written = log.read().lines().count();
+        assert_eq!(written + logger.discarded(), sent);
+    }
+}
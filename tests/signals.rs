@@ -0,0 +1,46 @@
+#![cfg(feature = "signals")]
+mod common;
+use common::TempLog;
+use glug::options::{AfterShutdown, OnSignal, Output};
+use glug::{Filter, GLogger, GLoggerOptions, GRecord};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+#[test]
+fn signals_end_logging_before_the_callback() {
+    let log = TempLog::new("signals");
+    let (caught, signals) = mpsc::channel();
+    let caught = Mutex::new(caught);
+    let gref = GLogger::setup_with_options(GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        filter: Filter::default(),
+        after_shutdown: AfterShutdown::Discard,
+        on_signal: Some(OnSignal::Call(Arc::new(move |signal| {
+            caught.lock().unwrap().send(signal).unwrap()
+        }))),
+        format: Arc::new(|record: &GRecord| record.message.clone()),
+        ..Default::default()
+    });
+    log::info!("before");
+    signal_hook::low_level::raise(signal_hook::consts::SIGTERM).unwrap();
+    let signal = signals.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(signal, signal_hook::consts::SIGTERM);
+    //everything is written by the time the callback runs
+    let written = log.read();
+    log::info!("after");
+    assert_eq!(gref.logger().discarded(), 1);
+    //later signals still reach the callback
+    signal_hook::low_level::raise(signal_hook::consts::SIGINT).unwrap();
+    let signal = signals.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(signal, signal_hook::consts::SIGINT);
+    drop(gref);
+    assert_eq!(written, "before\n");
+    //glug stops handling them once its logger is dropped, leaving them to the program
+    let handled = Arc::new(AtomicBool::new(false));
+    signal_hook::flag::register(signal_hook::consts::SIGTERM, handled.clone()).unwrap();
+    signal_hook::low_level::raise(signal_hook::consts::SIGTERM).unwrap();
+    assert!(handled.load(Ordering::SeqCst));
+    assert!(signals.recv_timeout(Duration::from_millis(200)).is_err());
+}
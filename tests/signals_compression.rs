@@ -0,0 +1,42 @@
+#![cfg(all(feature = "signals", feature = "gzip"))]
+mod common;
+use common::TempDir;
+use glug::options::{Compression, FileFormat, OnSignal, Output, Rotation};
+use glug::{GLogger, GLoggerOptions, GRecord, Template};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+#[test]
+fn archives_are_compressed_before_the_callback() {
+    let dir = TempDir::new("signals-compression");
+    let path = dir.path().join("app.log");
+    let (caught, listings) = mpsc::channel();
+    let caught = Mutex::new(caught);
+    let archive = path.with_extension("log.1");
+    let listed = archive.clone();
+    let gref = GLogger::setup_with_options(GLoggerOptions {
+        save_to_file: Some(path.to_string_lossy().into_owned()),
+        rotation: Some(Rotation {
+            max_bytes: Some(1024),
+            compression: Compression::Gzip,
+            ..Default::default()
+        }),
+        output: Output::Plain { colors: false },
+        filter: glug::Filter::default(),
+        on_signal: Some(OnSignal::Call(Arc::new(move |_| {
+            caught.lock().unwrap().send(listed.exists()).unwrap()
+        }))),
+        file_format: FileFormat::Template(Template::parse("{message}").unwrap()),
+        format: Arc::new(|record: &GRecord| format!("{} bytes", record.message.len())),
+        ..Default::default()
+    });
+    //big enough that compressing the archive is still going when the signal comes
+    let record = "x".repeat(16 << 20);
+    log::info!("{}", record);
+    log::info!("{}", record);
+    signal_hook::low_level::raise(signal_hook::consts::SIGTERM).unwrap();
+    let uncompressed = listings.recv_timeout(Duration::from_secs(30)).unwrap();
+    assert!(!uncompressed);
+    assert!(archive.with_extension("1.gz").exists());
+    drop(gref);
+}
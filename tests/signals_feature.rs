@@ -0,0 +1,21 @@
+#![cfg(not(feature = "signals"))]
+mod common;
+use common::TempLog;
+use glug::options::{OnSignal, Output};
+use glug::{GLogger, GLoggerOptions, GlugError};
+
+#[test]
+fn on_signal_needs_the_feature() {
+    let log = TempLog::new("signals_feature");
+    let result = GLogger::try_setup_with_options(GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        on_signal: Some(OnSignal::Reraise),
+        ..Default::default()
+    });
+    match result {
+        Err(GlugError::Signals(e)) => assert_eq!(e.kind(), std::io::ErrorKind::Unsupported),
+        _ => panic!("setup didn't fail"),
+    }
+    assert!(!log.path().exists());
+}
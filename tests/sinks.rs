@@ -0,0 +1,81 @@
+use glug::gstore::Writer;
+use glug::options::{FileFormat, GStoreOptions, Sink};
+use glug::{GRecord, GStore, Template};
+use log::{Level, LevelFilter};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Shared(Arc<Mutex<Vec<u8>>>);
+impl Write for Shared {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+impl Shared {
+    fn text(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+#[test]
+fn each_sink_has_its_own_level_and_format() {
+    let errors = Shared::default();
+    let everything = Shared::default();
+    let template = Template::parse("{level}: {message}").unwrap();
+    let sinks = [
+        Sink::writer(
+            errors.clone(),
+            LevelFilter::Error,
+            FileFormat::Template(template),
+        ),
+        Sink::writer(everything.clone(), LevelFilter::Trace, FileFormat::Json),
+    ];
+    let mut writers: Vec<Writer> = sinks
+        .iter()
+        .map(|sink| Writer {
+            format: sink.format.format(),
+            level: sink.level,
+            ..Writer::new(sink.target.open().unwrap())
+        })
+        .collect();
+    let format = |record: &GRecord| record.message.clone();
+    let mut store: GStore<std::thread::ThreadId> = GStoreOptions {
+        writers: &mut writers,
+        format: &format,
+        ..Default::default()
+    }
+    .into();
+    store.insert(GRecord::new(Level::Info, "started"));
+    store.insert(GRecord::new(Level::Error, "failed"));
+    drop(store);
+    assert_eq!(errors.text(), "ERROR: failed\n");
+    assert_eq!(
+        everything.text(),
+        "{\"level\":\"INFO\",\"target\":\"\",\"message\":\"started\"}\n\
+         {\"level\":\"ERROR\",\"target\":\"\",\"message\":\"failed\"}\n"
+    );
+}
+
+#[test]
+fn text_sinks_use_the_store_format() {
+    let out = Shared::default();
+    let mut writers = vec![Writer {
+        level: LevelFilter::Warn,
+        ..Writer::new(out.clone())
+    }];
+    let format = |record: &GRecord| format!("<{}>", record.message);
+    let mut store: GStore<std::thread::ThreadId> = GStoreOptions {
+        writers: &mut writers,
+        format: &format,
+        ..Default::default()
+    }
+    .into();
+    store.insert(GRecord::new(Level::Debug, "quiet"));
+    store.insert(GRecord::new(Level::Warn, "loud"));
+    drop(store);
+    assert_eq!(out.text(), "<loud>\n");
+}
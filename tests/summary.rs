@@ -0,0 +1,82 @@
+mod common;
+use common::TempLog;
+use glug::options::{GStoreOptions, KeyFn, Output, RecordThreadsOptions};
+use glug::{Filter, GLogger, GLoggerOptionalInfo, GLoggerOptions, GRecord, GStore};
+use log::Level;
+use std::sync::Arc;
+use std::thread::ThreadId;
+
+fn record(level: Level, message: &str, thread: ThreadId, name: Option<&str>) -> GRecord {
+    GRecord {
+        info: GLoggerOptionalInfo::new(Some((thread, name.map(str::to_owned))), None),
+        ..GRecord::new(level, message)
+    }
+}
+
+#[test]
+fn report_has_totals_threads_and_errors() {
+    let format = |record: &GRecord| record.message.clone();
+    let mut store: GStore<ThreadId> = GStoreOptions {
+        separate_log_counts: Some(Box::new(|record: &GRecord| record.thread_id()) as KeyFn<_>),
+        format: &format,
+        ..Default::default()
+    }
+    .into();
+    let main = std::thread::current().id();
+    let worker = std::thread::spawn(|| std::thread::current().id())
+        .join()
+        .unwrap();
+    store.insert(record(Level::Error, "first", main, Some("main")));
+    store.insert(record(Level::Info, "hello", worker, Some("worker")));
+    store.insert(record(Level::Warn, "careful", main, Some("main")));
+    store.insert(record(Level::Error, "second", worker, Some("worker")));
+    let report = store.report();
+    let lines: Vec<&str> = report.lines().collect();
+    assert!(lines[0].starts_with("summary: 4 records in "));
+    assert!(lines[0].ends_with("s, peak 4 records/s"));
+    assert_eq!(
+        lines[1..],
+        [
+            "levels: ERROR: 2, WARN: 1, INFO: 1, DEBUG: 0, TRACE: 0",
+            "threads: main: 2, worker: 2",
+            "first error: first",
+            "last error: second",
+        ]
+    );
+}
+
+#[test]
+fn report_is_written_when_logging_ends() {
+    let log = TempLog::new("summary");
+    let gref = GLogger::setup_with_options(GLoggerOptions {
+        save_to_file: log.save_to_file(),
+        output: Output::Plain { colors: false },
+        filter: Filter::default(),
+        record_threads: Some(RecordThreadsOptions {
+            separate_histograms: false,
+            summary: true,
+        }),
+        format: Arc::new(|record: &GRecord| record.message.clone()),
+        ..Default::default()
+    });
+    log::error!("oops");
+    std::thread::Builder::new()
+        .name("worker".to_string())
+        .spawn(|| log::info!("working"))
+        .unwrap()
+        .join()
+        .unwrap();
+    drop(gref);
+    let written = log.read();
+    let lines: Vec<&str> = written.lines().collect();
+    assert_eq!(lines[..2], ["oops", "working"]);
+    assert!(lines[2].starts_with("summary: 2 records in "));
+    assert!(lines.contains(&"levels: ERROR: 1, WARN: 0, INFO: 1, DEBUG: 0, TRACE: 0"));
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with("threads: ") && line.contains("worker: 1")));
+    assert_eq!(
+        lines[lines.len() - 2..],
+        ["first error: oops", "last error: oops"]
+    );
+}
@@ -0,0 +1,73 @@
+use chrono::TimeZone;
+use glug::{GLoggerOptionalInfo, GRecord, Template};
+use log::Level;
+
+fn record() -> GRecord {
+    let mut record = GRecord::new(Level::Info, "connected");
+    record.target = "app::net".to_string();
+    record.module_path = Some("app::net::tcp".to_string());
+    record.file = Some("src/net/tcp.rs".to_string());
+    record.line = Some(42);
+    record.info = GLoggerOptionalInfo::new(
+        Some((std::thread::current().id(), Some("io".to_string()))),
+        Some(
+            chrono::Local
+                .with_ymd_and_hms(2026, 10, 18, 12, 30, 5)
+                .unwrap(),
+        ),
+    );
+    record
+}
+
+#[test]
+fn renders_every_placeholder() {
+    let template = Template::parse(
+        "{time:%H:%M:%S} {level:<5} [{thread}] {target} {module} {file}:{line}: {message}",
+    )
+    .unwrap();
+    assert_eq!(
+        template.render(&record()),
+        "12:30:05 INFO  [io] app::net app::net::tcp src/net/tcp.rs:42: connected"
+    );
+}
+
+#[test]
+fn location_falls_back_to_the_module() {
+    let template = Template::parse("{location}").unwrap();
+    let mut record = record();
+    assert_eq!(template.render(&record), "src/net/tcp.rs:42");
+    record.file = None;
+    assert_eq!(template.render(&record), "app::net::tcp");
+}
+
+#[test]
+fn aligns_and_escapes() {
+    let template = Template::parse("{{{level:>6}}} {level:^7}|{message:12}|").unwrap();
+    assert_eq!(template.render(&record()), "{  INFO}  INFO  |connected   |");
+}
+
+#[test]
+fn missing_info_renders_empty() {
+    let template = Template::parse("[{thread}]{time} {file}{line} {message}").unwrap();
+    assert_eq!(
+        template.render(&GRecord::new(Level::Error, "oops")),
+        "[]  oops"
+    );
+}
+
+#[test]
+fn invalid_templates_are_errors() {
+    let error = Template::parse("{level} {mesage}").unwrap_err();
+    assert_eq!(error.position, 8);
+    assert!(error.to_string().contains("unknown placeholder `mesage`"));
+    assert!(Template::parse("{level").is_err());
+    assert!(Template::parse("level}").is_err());
+    assert!(Template::parse("{level:~5}").is_err());
+    assert!(Template::parse("{time:%Q}").is_err());
+}
+
+#[test]
+fn becomes_a_formatter() {
+    let format = Template::parse("{level}: {message}").unwrap().into_format();
+    assert_eq!(format(&record()), "INFO: connected");
+}
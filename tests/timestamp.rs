@@ -0,0 +1,92 @@
+use chrono::{Duration, TimeZone, Utc};
+use glug::options::{GStoreOptions, TimestampFormat, TimestampStyle};
+use glug::{DivNode, GLoggerOptionalInfo, GRecord, GStore, Template};
+use log::Level;
+use std::sync::Arc;
+
+fn at(style: TimestampStyle, utc: bool) -> TimestampFormat {
+    TimestampFormat { style, utc }
+}
+
+#[test]
+fn renders_every_style() {
+    let started = Utc
+        .with_ymd_and_hms(2026, 10, 18, 12, 0, 0)
+        .unwrap()
+        .with_timezone(&chrono::Local);
+    let previous = started + Duration::milliseconds(1500);
+    let time = started + Duration::milliseconds(61_234);
+    let render = |format: TimestampFormat| format.render(&time, &started, Some(&previous));
+    assert_eq!(
+        render(at(TimestampStyle::Strftime("%H:%M:%S%.3f".into()), true)),
+        "12:01:01.234"
+    );
+    assert_eq!(
+        render(at(TimestampStyle::Rfc3339, true)),
+        "2026-10-18T12:01:01.234Z"
+    );
+    assert_eq!(render(at(TimestampStyle::Elapsed, false)), "+61.234s");
+    assert_eq!(render(at(TimestampStyle::Delta, false)), "+59.734s");
+    assert_eq!(
+        at(TimestampStyle::Delta, false).render(&time, &time, None),
+        "+0.000s"
+    );
+}
+
+#[test]
+fn invalid_patterns_do_not_panic() {
+    let time = chrono::Local::now();
+    let rendered = at(TimestampStyle::Strftime("%Q".into()), false).render(&time, &time, None);
+    assert_eq!(
+        rendered,
+        at(TimestampStyle::Rfc3339, false).render(&time, &time, None)
+    );
+}
+
+#[test]
+fn store_applies_the_format_to_lines_and_summary() {
+    let format =
+        |record: &GRecord| format!("{} {}", record.timestamp_text().unwrap(), record.message);
+    let mut store: GStore<std::thread::ThreadId> = GStoreOptions {
+        format: &format,
+        timestamp_format: at(TimestampStyle::Strftime("%Y".into()), false),
+        ..Default::default()
+    }
+    .into();
+    let mut record = GRecord::new(Level::Info, "hi");
+    record.info = GLoggerOptionalInfo::new(None, Some(chrono::Local::now()));
+    store.insert(record);
+    let year = chrono::Local::now().format("%Y").to_string();
+    assert_eq!(store.logs()[0].0, format!("{} hi", year));
+    let frame = DivNode::Element(Arc::new(glug::elements::summary))
+        .render(40, 1, &store)
+        .unwrap();
+    assert_eq!(frame.text(), format!("      total: 1, last: {}", year));
+}
+
+#[test]
+fn utc_applies_to_templates_and_json() {
+    let time = Utc
+        .with_ymd_and_hms(2026, 10, 18, 12, 0, 0)
+        .unwrap()
+        .with_timezone(&chrono::Local);
+    let insert = |format: &dyn Fn(&GRecord) -> String| {
+        let mut store: GStore<std::thread::ThreadId> = GStoreOptions {
+            format,
+            timestamp_format: at(TimestampStyle::Rfc3339, true),
+            ..Default::default()
+        }
+        .into();
+        let mut record = GRecord::new(Level::Info, "hi");
+        record.info = GLoggerOptionalInfo::new(None, Some(time));
+        store.insert(record);
+        store.logs()[0].0.clone()
+    };
+    let template = Template::parse("{time:%H:%M} {message}")
+        .unwrap()
+        .into_format();
+    assert_eq!(insert(&*template), "12:00 hi");
+    assert!(
+        insert(&glug::options::json_format).contains(r#""timestamp":"2026-10-18T12:00:00.000Z""#)
+    );
+}
@@ -0,0 +1,94 @@
+use glug::elements::summary;
+use glug::gstore::Writer;
+use glug::options::{GStoreOptions, Reopen};
+use glug::{DivNode, GRecord, GStore};
+use log::Level;
+use std::cell::RefCell;
+use std::io::{Error, Write};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Default)]
+struct Shared(Rc<RefCell<Vec<u8>>>);
+impl Write for Shared {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+struct Full;
+impl Write for Full {
+    fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+        Err(Error::other("disk full"))
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn failing_writer(reopened: &Shared) -> Writer {
+    let reopened = reopened.clone();
+    Writer {
+        name: "app.log".to_string(),
+        reopen: Some(Box::new(move || Ok(Box::new(reopened.clone())))),
+        ..Writer::new(Full)
+    }
+}
+
+#[test]
+fn failures_are_counted_reported_and_reopened() {
+    let reopened = Shared::default();
+    let reported = Arc::new(Mutex::new(vec![]));
+    let mut writers = vec![failing_writer(&reopened)];
+    let format = |record: &GRecord| record.message.clone();
+    let mut store: GStore<std::thread::ThreadId> = GStoreOptions {
+        writers: &mut writers,
+        format: &format,
+        reopen: Reopen::After(Duration::ZERO),
+        on_write_error: Some(Arc::new({
+            let reported = reported.clone();
+            move |name: &str, error: &Error| {
+                reported.lock().unwrap().push(format!("{} {}", name, error))
+            }
+        })),
+        ..Default::default()
+    }
+    .into();
+    store.insert(GRecord::new(Level::Info, "lost"));
+    assert_eq!(store.write_errors(), 1);
+    assert_eq!(*reported.lock().unwrap(), ["app.log disk full"]);
+    let frame = DivNode::Element(Arc::new(summary))
+        .render(60, 1, &store)
+        .unwrap();
+    assert_eq!(
+        frame.lines()[0],
+        "      total: 1, write errors: 1 (app.log: disk full)"
+    );
+    store.insert(GRecord::new(Level::Info, "kept"));
+    drop(store);
+    assert_eq!(
+        String::from_utf8(reopened.0.borrow().clone()).unwrap(),
+        "kept\n"
+    );
+}
+
+#[test]
+fn never_reopening_leaves_writers_off() {
+    let reopened = Shared::default();
+    let mut writers = vec![failing_writer(&reopened)];
+    let mut store: GStore<std::thread::ThreadId> = GStoreOptions {
+        writers: &mut writers,
+        reopen: Reopen::Never,
+        ..Default::default()
+    }
+    .into();
+    store.insert(GRecord::new(Level::Info, "lost"));
+    store.insert(GRecord::new(Level::Info, "also lost"));
+    store.flush();
+    assert_eq!(store.write_errors(), 1);
+    drop(store);
+    assert!(reopened.0.borrow().is_empty());
+}